@@ -0,0 +1,46 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+//! Implementations of arbitrary's `Arbitrary` trait, enabled using the
+//! `arbitrary` feature.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use {math, Multiple, Size};
+
+/// Generates any of the multiples.
+impl<'a> Arbitrary<'a> for Multiple {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Multiple> {
+        u.choose(Multiple::all()).cloned()
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u32::size_hint(depth)
+    }
+}
+
+/// Generates valid sizes, i.e. sizes that could be created using `Size::new`,
+/// with a non-negative value. Zero and the maximum value are generated more
+/// often than other values.
+impl<'a> Arbitrary<'a> for Size {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Size> {
+        let value = match u.int_in_range(0u8..=3)? {
+            0 => 0.0,
+            1 => f64::MAX,
+            2 => f64::from(u32::arbitrary(u)?),
+            _ => {
+                let value = math::abs(f64::arbitrary(u)?);
+                if value.is_normal() { value } else { 0.0 }
+            },
+        };
+        Ok(Size {
+            value,
+            multiple: Multiple::arbitrary(u)?,
+            convention: None,
+        })
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+use core::fmt;
+use core::cmp::Ordering;
+use core::str::FromStr;
+use core::time::Duration;
+
+use {Multiple, ParsingError, Size, Throughput};
+
+/// `BitRate` represents a rate of bits per second, as used by network
+/// equipment, e.g. "100 Mbps". `BitRate` can be created using the `new`
+/// function, or parsed from a string using the [`FromStr`] trait.
+///
+/// Bit rates always use decimal multiples, see [`BitMultiple`]. Next to the
+/// "Mbps" form the "Mbit/s" form is also accepted when parsing. Note that
+/// multiples are case sensitive: a lowercase "b" means bits, while an
+/// uppercase "B", e.g. "MB/s", means bytes, which is not accepted here, use
+/// [`Throughput`] instead.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::BitRate;
+/// let bit_rate = "100 Mbps".parse::<BitRate>().unwrap();
+/// println!("bit rate: {}", bit_rate); // 100 Mbps
+/// println!("bytes per second: {}", bit_rate.bytes_per_second()); // 12500000
+/// # }
+/// ```
+///
+/// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+/// [`BitMultiple`]: enum.BitMultiple.html
+/// [`Throughput`]: struct.Throughput.html
+#[derive(Copy, Clone, Debug)]
+pub struct BitRate {
+    value: f64,
+    multiple: BitMultiple,
+}
+
+impl BitRate {
+    /// Create a new `BitRate` of `value` times `multiple` bits per second.
+    /// Like [`Size::new`] this returns an error if the value is not valid.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{BitRate, BitMultiple};
+    /// let bit_rate = BitRate::new(1, BitMultiple::Gigabit).unwrap();
+    /// println!("bit rate: {}", bit_rate); // 1 Gbps
+    /// # }
+    /// ```
+    ///
+    /// [`Size::new`]: struct.Size.html#method.new
+    #[allow(clippy::result_unit_err)]
+    pub fn new<V>(value: V, multiple: BitMultiple) -> Result<BitRate, ()>
+        where V: Into<f64>,
+    {
+        let value = value.into();
+        // Zero is not considered normal, but should be accepted here.
+        if !value.is_normal() && value != 0.0 {
+            Err(())
+        } else {
+            Ok(BitRate { value, multiple })
+        }
+    }
+
+    /// The number of bits per second.
+    pub fn bits_per_second(&self) -> f64 {
+        self.value * self.multiple.multiple_of_bits() as f64
+    }
+
+    /// The number of bytes per second, i.e. the number of bits per second
+    /// divided by eight.
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bits_per_second() / 8.0
+    }
+
+    /// Convert the `BitRate` into a [`Throughput`] per second, using the
+    /// decimal byte multiple matching the multiple of the bit rate.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::BitRate;
+    /// let bit_rate = "80 Mbps".parse::<BitRate>().unwrap();
+    /// println!("throughput: {}", bit_rate.to_throughput()); // 10 MB/s
+    /// # }
+    /// ```
+    ///
+    /// [`Throughput`]: struct.Throughput.html
+    pub fn to_throughput(&self) -> Throughput {
+        let multiple = match self.multiple {
+            BitMultiple::Bit => Multiple::Byte,
+            BitMultiple::Kilobit => Multiple::Kilobyte,
+            BitMultiple::Megabit => Multiple::Megabyte,
+            BitMultiple::Gigabit => Multiple::Gigabyte,
+            BitMultiple::Terabit => Multiple::Terabyte,
+        };
+        let size = Size { value: self.value / 8.0, multiple, convention: None };
+        Throughput::new(size, Duration::from_secs(1))
+    }
+}
+
+impl Eq for BitRate {}
+
+impl PartialEq for BitRate {
+    fn eq(&self, other: &BitRate) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for BitRate {
+    fn partial_cmp(&self, other: &BitRate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Bit rates are compared by their number of bits per second.
+impl Ord for BitRate {
+    fn cmp(&self, other: &BitRate) -> Ordering {
+        compare_bits((self.value, self.multiple), (other.value, other.multiple))
+    }
+}
+
+impl FromStr for BitRate {
+    type Err = ParsingError;
+
+    fn from_str(input: &str) -> Result<BitRate, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(ParsingError::EmptyInput);
+        }
+
+        let (value, _, multiple_part) = Size::parse_value_tokens(input)
+            .map_err(|(kind, _)| kind)?;
+        let multiple = match multiple_part.trim_start() {
+            "" => return Err(ParsingError::MissingMultiple),
+            multiple => multiple.parse()?,
+        };
+        BitRate::new(value, multiple).map_err(|()| ParsingError::InvalidValue)
+    }
+}
+
+impl fmt::Display for BitRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.multiple)
+    }
+}
+
+/// A `BitMultiple` represents a (decimal) multiple of bits, used by [`Bits`],
+/// or of bits per second, used by [`BitRate`]. The symbols depend on the type
+/// using the multiple, e.g. "Mbit" for `Bits` and "Mbps" for `BitRate`.
+///
+/// [`Bits`]: struct.Bits.html
+/// [`BitRate`]: struct.BitRate.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BitMultiple {
+    /// Represents a single bit, value * 1, "bit" or "bps" when parsing text.
+    Bit,
+
+    /// Represents a single kilobit, value * 1000, "kbit" or "kbps" when
+    /// parsing text.
+    Kilobit,
+
+    /// Represents a single megabit, value * 1000^2, "Mbit" or "Mbps" when
+    /// parsing text.
+    Megabit,
+
+    /// Represents a single gigabit, value * 1000^3, "Gbit" or "Gbps" when
+    /// parsing text.
+    Gigabit,
+
+    /// Represents a single terabit, value * 1000^4, "Tbit" or "Tbps" when
+    /// parsing text.
+    Terabit,
+}
+
+impl BitMultiple {
+    /// The symbol of the multiple, e.g. "Mbps" for `Megabit`.
+    fn symbol(self) -> &'static str {
+        match self {
+            BitMultiple::Bit => "bps",
+            BitMultiple::Kilobit => "kbps",
+            BitMultiple::Megabit => "Mbps",
+            BitMultiple::Gigabit => "Gbps",
+            BitMultiple::Terabit => "Tbps",
+        }
+    }
+
+    /// The symbol of the multiple used by [`Bits`], e.g. "Mbit" for
+    /// `Megabit`.
+    ///
+    /// [`Bits`]: struct.Bits.html
+    pub(crate) fn bits_symbol(self) -> &'static str {
+        match self {
+            BitMultiple::Bit => "bit",
+            BitMultiple::Kilobit => "kbit",
+            BitMultiple::Megabit => "Mbit",
+            BitMultiple::Gigabit => "Gbit",
+            BitMultiple::Terabit => "Tbit",
+        }
+    }
+
+    pub(crate) fn multiple_of_bits(self) -> u64 {
+        match self {
+            BitMultiple::Bit => 1,
+            BitMultiple::Kilobit => 1000,
+            BitMultiple::Megabit => 1000u64.pow(2),
+            BitMultiple::Gigabit => 1000u64.pow(3),
+            BitMultiple::Terabit => 1000u64.pow(4),
+        }
+    }
+}
+
+/// Compare two values, given as value and multiple, by their number of bits,
+/// used by [`Bits`] and [`BitRate`]. Like `Size::compare`, if either value
+/// overflows when converted into bits the value with the smaller multiple is
+/// converted into the larger multiple instead, which can't overflow.
+///
+/// [`Bits`]: struct.Bits.html
+/// [`BitRate`]: struct.BitRate.html
+pub(crate) fn compare_bits(left: (f64, BitMultiple), right: (f64, BitMultiple)) -> Ordering {
+    let (left_multiple, right_multiple) = (left.1.multiple_of_bits() as f64, right.1.multiple_of_bits() as f64);
+    let (left_bits, right_bits) = (left.0 * left_multiple, right.0 * right_multiple);
+    let (left, right) = if left_bits.is_finite() && right_bits.is_finite() {
+        (left_bits, right_bits)
+    } else if left_multiple >= right_multiple {
+        (left.0, right.0 * (right_multiple / left_multiple))
+    } else {
+        (left.0 * (left_multiple / right_multiple), right.0)
+    };
+    // Values are never NaN (see `BitRate::new` and `Bits::new`), and zero is
+    // equal to negative zero.
+    left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+}
+
+impl FromStr for BitMultiple {
+    type Err = ParsingError;
+
+    fn from_str(input: &str) -> Result<BitMultiple, Self::Err> {
+        match input {
+            "bps" | "bit/s" => Ok(BitMultiple::Bit),
+            "kbps" | "Kbps" | "kbit/s" => Ok(BitMultiple::Kilobit),
+            "Mbps" | "Mbit/s" => Ok(BitMultiple::Megabit),
+            "Gbps" | "Gbit/s" => Ok(BitMultiple::Gigabit),
+            "Tbps" | "Tbit/s" => Ok(BitMultiple::Terabit),
+            _ => Err(ParsingError::InvalidMultiple),
+        }
+    }
+}
+
+impl fmt::Display for BitMultiple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.symbol())
+    }
+}
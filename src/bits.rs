@@ -0,0 +1,191 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+use core::fmt;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::str::FromStr;
+
+use bit_rate::compare_bits;
+use {BitMultiple, ConversionError, Multiple, ParsingError, Size};
+
+/// `Bits` represents a number of bits in a (decimal) multiple of bits, e.g.
+/// "100 Mbit". It's separate from [`Size`], which uses multiples of bytes, so
+/// that bits and bytes can't be confused. Use [`to_size`] and [`from_size`] to
+/// convert between the two, i.e. "8 bit" is "1 B".
+///
+/// `Bits` can be created using the `new` function, or parsed from a string
+/// using the [`FromStr`] trait. The supported multiples are "bit", "kbit",
+/// "Mbit", "Gbit" and "Tbit", see [`BitMultiple`]. Values are parsed the
+/// same way as for [`Size`], e.g. "1_000 bit" and "1e3 bit" are accepted.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Bits, Size, Multiple};
+/// let bits = "8 bit".parse::<Bits>().unwrap();
+/// println!("bits: {}", bits); // 8 bit
+/// assert_eq!(bits.to_size(), Size::new(1, Multiple::Byte).unwrap());
+/// # }
+/// ```
+///
+/// [`Size`]: struct.Size.html
+/// [`to_size`]: #method.to_size
+/// [`from_size`]: #method.from_size
+/// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+/// [`BitMultiple`]: enum.BitMultiple.html
+#[derive(Copy, Clone, Debug)]
+pub struct Bits {
+    value: f64,
+    multiple: BitMultiple,
+}
+
+impl Bits {
+    /// Create a new `Bits` of `value` times `multiple` bits. Like
+    /// [`Size::new`] this returns an error if the value is not valid.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Bits, BitMultiple};
+    /// let bits = Bits::new(100, BitMultiple::Megabit).unwrap();
+    /// println!("bits: {}", bits); // 100 Mbit
+    /// # }
+    /// ```
+    ///
+    /// [`Size::new`]: struct.Size.html#method.new
+    #[allow(clippy::result_unit_err)]
+    pub fn new<V>(value: V, multiple: BitMultiple) -> Result<Bits, ()>
+        where V: Into<f64>,
+    {
+        let value = value.into();
+        // Zero is not considered normal, but should be accepted here.
+        if !value.is_normal() && value != 0.0 {
+            Err(())
+        } else {
+            Ok(Bits { value, multiple })
+        }
+    }
+
+    /// Create `Bits` from `size`, expressed in (single) bits.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Bits, Size, Multiple};
+    /// let size = Size::new(1, Multiple::Kilobyte).unwrap();
+    /// println!("bits: {}", Bits::from_size(size)); // 8000 bit
+    /// # }
+    /// ```
+    pub fn from_size(size: Size) -> Bits {
+        Bits { value: size.into_bytes() * 8.0, multiple: BitMultiple::Bit }
+    }
+
+    /// Convert the `Bits` into the number of bits, be wary of overflows!
+    pub fn into_bits(self) -> f64 {
+        self.value * self.multiple.multiple_of_bits() as f64
+    }
+
+    /// Convert the `Bits` into a number of (whole) bits as `u64`. Fractional
+    /// bits are truncated. Returns an error if the number of bits is negative
+    /// or doesn't fit in an `u64`, like [`Size::to_integer`].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Bits, BitMultiple, ConversionError};
+    /// let bits = Bits::new(100, BitMultiple::Megabit).unwrap();
+    /// assert_eq!(bits.to_bits_u64(), Ok(100_000_000));
+    ///
+    /// let bits = Bits::new(-1, BitMultiple::Bit).unwrap();
+    /// assert_eq!(bits.to_bits_u64(), Err(ConversionError::Negative));
+    /// # }
+    /// ```
+    ///
+    /// [`Size::to_integer`]: struct.Size.html#method.to_integer
+    pub fn to_bits_u64(&self) -> Result<u64, ConversionError> {
+        let bits = self.into_bits();
+        if bits < 0.0 {
+            Err(ConversionError::Negative)
+        } else if bits >= u64::MAX as f64 {
+            // `u64::MAX` rounds up to 2^64 as `f64`, which doesn't fit.
+            Err(ConversionError::Overflow)
+        } else {
+            Ok(bits as u64)
+        }
+    }
+
+    /// Convert the `Bits` into a [`Size`] in (fractional) bytes, i.e. the
+    /// number of bits divided by eight.
+    ///
+    /// [`Size`]: struct.Size.html
+    pub fn to_size(&self) -> Size {
+        Size { value: self.into_bits() / 8.0, multiple: Multiple::Byte, convention: None }
+    }
+}
+
+impl Eq for Bits {}
+
+impl PartialEq for Bits {
+    fn eq(&self, other: &Bits) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Bits {
+    fn partial_cmp(&self, other: &Bits) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Bits are compared by their number of bits.
+impl Ord for Bits {
+    fn cmp(&self, other: &Bits) -> Ordering {
+        compare_bits((self.value, self.multiple), (other.value, other.multiple))
+    }
+}
+
+/// Converts the `Bits` into a number of bits, see [`Bits::to_bits_u64`].
+///
+/// [`Bits::to_bits_u64`]: struct.Bits.html#method.to_bits_u64
+impl TryFrom<Bits> for u64 {
+    type Error = ConversionError;
+
+    fn try_from(bits: Bits) -> Result<u64, ConversionError> {
+        bits.to_bits_u64()
+    }
+}
+
+impl FromStr for Bits {
+    type Err = ParsingError;
+
+    fn from_str(input: &str) -> Result<Bits, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(ParsingError::EmptyInput);
+        }
+
+        let (value, _, multiple_part) = Size::parse_value_tokens(input)
+            .map_err(|(kind, _)| kind)?;
+        let multiple = match multiple_part.trim_start() {
+            "" => return Err(ParsingError::MissingMultiple),
+            "bit" | "bits" => BitMultiple::Bit,
+            "kbit" => BitMultiple::Kilobit,
+            "Mbit" => BitMultiple::Megabit,
+            "Gbit" => BitMultiple::Gigabit,
+            "Tbit" => BitMultiple::Terabit,
+            _ => return Err(ParsingError::InvalidMultiple),
+        };
+        Bits::new(value, multiple).map_err(|()| ParsingError::InvalidValue)
+    }
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.multiple.bits_symbol())
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+//! Implementations of clap's `ValueParserFactory` and `TypedValueParser`
+//! traits, enabled using the `clap` feature.
+
+use core::fmt::Write;
+use std::ffi::OsStr;
+use std::string::String;
+
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::{Error, ErrorKind};
+use clap::{Arg, Command};
+
+use {ParseOptions, Size};
+
+/// Parser for command line arguments, used by clap's `value_parser!` macro
+/// for [`Size`], e.g. to parse `--max-size 10MB`. Invalid sizes are reported
+/// as clap errors, using the [`ParsingError`] as reason.
+///
+/// By default sizes are parsed the same way as the `FromStr` implementation
+/// of `Size`, use [`with_options`] to parse them using [`ParseOptions`].
+///
+/// ```
+/// # extern crate clap;
+/// # extern crate human_size;
+/// # fn main() {
+/// use clap::{value_parser, Arg, Command};
+/// use human_size::{Size, Multiple};
+///
+/// let cmd = Command::new("app")
+///     .arg(Arg::new("max-size").long("max-size").value_parser(value_parser!(Size)));
+/// let matches = cmd.try_get_matches_from(["app", "--max-size", "10MB"]).unwrap();
+/// let size = matches.get_one::<Size>("max-size").unwrap();
+/// assert_eq!(*size, Size::new(10, Multiple::Megabyte).unwrap());
+/// # }
+/// ```
+///
+/// [`Size`]: ../struct.Size.html
+/// [`ParsingError`]: ../enum.ParsingError.html
+/// [`ParseOptions`]: ../struct.ParseOptions.html
+/// [`with_options`]: #method.with_options
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SizeValueParser {
+    options: ParseOptions,
+}
+
+impl SizeValueParser {
+    /// Create a new `SizeValueParser`, which parses sizes the same way as the
+    /// `FromStr` implementation of `Size`.
+    pub fn new() -> SizeValueParser {
+        SizeValueParser { options: ParseOptions::new() }
+    }
+
+    /// Create a new `SizeValueParser` that parses sizes using `options`, see
+    /// [`Size::parse_with`].
+    ///
+    /// [`Size::parse_with`]: ../struct.Size.html#method.parse_with
+    pub fn with_options(options: ParseOptions) -> SizeValueParser {
+        SizeValueParser { options }
+    }
+}
+
+impl TypedValueParser for SizeValueParser {
+    type Value = Size;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Size, Error> {
+        let input = value.to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        Size::parse_with(input, &self.options).map_err(|err| {
+            let mut message = String::new();
+            let _ = match arg {
+                Some(arg) => write!(message, "invalid value '{}' for '{}': {}", input, arg, err),
+                None => write!(message, "invalid value '{}': {}", input, err),
+            };
+            Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        })
+    }
+}
+
+/// Uses [`SizeValueParser`], which makes `value_parser!(Size)`, and fields
+/// of type `Size` when using clap's derive macros, work.
+///
+/// [`SizeValueParser`]: struct.SizeValueParser.html
+impl ValueParserFactory for Size {
+    type Parser = SizeValueParser;
+
+    fn value_parser() -> SizeValueParser {
+        SizeValueParser::new()
+    }
+}
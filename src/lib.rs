@@ -5,10 +5,9 @@
 // or http://opensource.org/licenses/MIT>, at your option. This file may not be
 // used, copied, modified, or distributed except according to those terms.
 
+#![no_std]
 #![warn(missing_docs)]
 
-// TODO: implement serde.
-
 //! The `human_size` represents sizes for humans. The main type is [`Size`],
 //! which (as the name might suggests) represents a size in multiple of bytes.
 //!
@@ -30,11 +29,90 @@
 //! println!("size in bytes: {}", bytes); // 102400
 //! # }
 //! ```
+//!
+//! # Features
+//!
+//! Only the `std` feature is enabled by default.
+//!
+//! * `std`: uses the float functions from the standard library and
+//!   implements its `Error` trait for the error types, implies `alloc`.
+//!   Without it the crate is `no_std`.
+//! * `alloc`: enables the methods that return a `String` or `Vec`, e.g.
+//!   [`Size::format`], and the ones that need a temporary allocation, such as
+//!   [`Size::parse_localized`] and [`sort_sizes`].
+//! * `serde`: implements serde's `Serialize` and `Deserialize` for [`Size`]
+//!   and [`Multiple`], using the same string as their [`Display`] and
+//!   [`FromStr`] implementations, e.g. "1 kB". A `Size` can also be
+//!   deserialized from an integer number of bytes. Parsing errors are
+//!   reported as custom serde errors.
+//! * `arbitrary`: implements arbitrary's `Arbitrary` for [`Size`] and
+//!   [`Multiple`], for use in fuzzing.
+//! * `clap`: implements clap's `ValueParserFactory` for [`Size`], using
+//!   [`SizeValueParser`], so that command line arguments such as
+//!   `--max-size 10MB` can be parsed directly into a `Size`. Invalid sizes are
+//!   reported as clap errors.
+//!
+//! The minimum supported Rust version is 1.62, with and without the `std`
+//! feature. The `arbitrary` and `clap` features require the Rust version of
+//! the respective crates.
+//!
+//! [`Multiple`]: enum.Multiple.html
+//! [`Size::format`]: struct.Size.html#method.format
+//! [`Size::parse_localized`]: struct.Size.html#method.parse_localized
+//! [`sort_sizes`]: fn.sort_sizes.html
+//! [`SizeValueParser`]: struct.SizeValueParser.html
+//! [`Display`]: https://doc.rust-lang.org/nightly/core/fmt/trait.Display.html
+//! [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+
+#[cfg(any(feature = "std", feature = "clap"))]
+extern crate std;
+#[cfg(feature = "alloc")]
+#[macro_use]
+extern crate alloc;
+
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, Sum};
+use core::str::{self, FromStr};
+use core::cmp::{self, Ordering};
+use core::convert::TryFrom;
+use core::ops::{Add, AddAssign, Div, Mul, Range, Sub, SubAssign};
+use core::time::Duration;
 
-use std::fmt;
-use std::error::Error;
-use std::str::FromStr;
-use std::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "clap")]
+extern crate clap;
+#[cfg(feature = "serde")]
+extern crate serde;
+
+mod bit_rate;
+mod bits;
+mod math;
+mod throughput;
+mod writers;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+#[cfg(feature = "clap")]
+mod clap_impls;
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use bit_rate::{BitMultiple, BitRate};
+pub use bits::Bits;
+pub use throughput::Throughput;
+#[cfg(feature = "clap")]
+pub use clap_impls::SizeValueParser;
 
 /// `Size` represents a size in bytes. `Size` can be created using the `new`
 /// function, or parsed from a string using the [`FromStr`] trait.
@@ -43,6 +121,10 @@ use std::cmp::Ordering;
 /// created with a [`Multiple`] of `Byte` and another of `Kilobyte` it won't
 /// matter.
 ///
+/// Sizes in bits, e.g. "12 bit", can also be parsed, they're converted into
+/// (fractional) bytes, e.g. "1.5 B". Values may use underscores to separate
+/// digits, e.g. "1_000_000 B", and scientific notation, e.g. "2.5e6 B".
+///
 /// ```
 /// # extern crate human_size;
 /// # fn main() {
@@ -59,13 +141,57 @@ use std::cmp::Ordering;
 pub struct Size {
     value: f64,
     multiple: Multiple,
+    convention: Option<Convention>,
 }
 
 impl Size {
+    /// A size of zero bytes, the same as the [`Default`] `Size`.
+    ///
+    /// [`Default`]: https://doc.rust-lang.org/nightly/core/default/trait.Default.html
+    pub const ZERO: Size = Size {
+        value: 0.0,
+        multiple: Multiple::Byte,
+        convention: None,
+    };
+
+    /// The largest size, the largest value in the largest multiple, i.e.
+    /// `f64::MAX` pebibytes. No `Size` compares larger than this.
+    ///
+    /// Note that its number of bytes doesn't fit in an `f64`, so
+    /// [`into_bytes`] returns infinity and [`as_bytes`] saturates at
+    /// `u128::MAX`. Arithmetic saturates at this bound: adding a size that is
+    /// small compared to `MAX`, e.g. one byte, is rounded away and returns
+    /// `MAX` unchanged, while adding a size that is large enough to overflow,
+    /// e.g. `MAX` itself, fails.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// assert_eq!(Size::MAX.into_bytes(), f64::INFINITY);
+    /// let one_byte = Size::new(1, Multiple::Byte).unwrap();
+    /// assert_eq!(Size::MAX.checked_add(one_byte), Some(Size::MAX));
+    /// assert_eq!(Size::MAX.checked_add(Size::MAX), None);
+    /// # }
+    /// ```
+    ///
+    /// [`into_bytes`]: #method.into_bytes
+    /// [`as_bytes`]: #method.as_bytes
+    pub const MAX: Size = Size {
+        value: f64::MAX,
+        multiple: Multiple::Pebibyte,
+        convention: None,
+    };
+
     /// Create a new `Size` with the multiple of bytes and the value. If the
     /// `value` is [not normal] this will return an error, zero is allowed. If
     /// the `value` is normal the result can be safely unwraped.
     ///
+    /// The value is stored as `f64`, so it isn't limited to the range of
+    /// `u32`, e.g. 5 billion bytes is a valid size, and integers up to 2^53
+    /// are represented exactly. Since `u64` doesn't implement `Into<f64>`
+    /// larger integers must be passed as float, or use [`from_bytes`].
+    ///
     /// ```
     /// # extern crate human_size;
     /// # fn main() {
@@ -81,6 +207,8 @@ impl Size {
     /// ```
     ///
     /// [not normal]: https://doc.rust-lang.org/nightly/std/primitive.f64.html#method.is_normal
+    /// [`from_bytes`]: #method.from_bytes
+    #[allow(clippy::result_unit_err)]
     pub fn new<V>(value: V, multiple: Multiple) -> Result<Size, ()>
         where V: Into<f64>,
     {
@@ -90,12 +218,147 @@ impl Size {
             Err(())
         } else {
             Ok(Size {
-                value: value,
+                value,
                 multiple,
+                convention: None,
             })
         }
     }
 
+    /// Returns the value of the `Size`, in its multiple, see [`multiple`].
+    /// Like the multiple this is always the value the `Size` was created, or
+    /// parsed, with.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = "1.5 GiB".parse::<Size>().unwrap();
+    /// assert_eq!(size.value(), 1.5);
+    /// assert_eq!(size.multiple(), Multiple::Gigibyte);
+    /// # }
+    /// ```
+    ///
+    /// [`multiple`]: #method.multiple
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the multiple of the `Size`. This is always the multiple the
+    /// `Size` was created, or parsed, with; operations such as comparing
+    /// never change it, operations that use another multiple, such as
+    /// [`humanize`], return a new `Size`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = "1 GiB".parse::<Size>().unwrap();
+    /// assert_eq!(size.multiple(), Multiple::Gigibyte);
+    /// # }
+    /// ```
+    ///
+    /// [`humanize`]: #method.humanize
+    pub fn multiple(&self) -> Multiple {
+        self.multiple
+    }
+
+    /// Create a new `Size` from an integer `value` and `multiple`, returning
+    /// `None` if the size in bytes, i.e. `value` times `multiple`, doesn't
+    /// fit in an `u128`. This includes values that only overflow after being
+    /// rounded to the nearest `f64`, which is how the value is stored. This
+    /// ensures that the size can always be converted into an integer using
+    /// [`to_integer::<u128>`].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::checked_from_parts(10, Multiple::Pebibyte).unwrap();
+    /// println!("size: {}", size); // 10 PiB
+    ///
+    /// assert!(Size::checked_from_parts(u128::MAX, Multiple::Kilobyte).is_none());
+    /// # }
+    /// ```
+    ///
+    /// [`to_integer::<u128>`]: #method.to_integer
+    pub fn checked_from_parts(value: u128, multiple: Multiple) -> Option<Size> {
+        value.checked_mul(u128::from(multiple.multiple_of_bytes()))?;
+        let size = Size { value: value as f64, multiple, convention: None };
+        if size.into_bytes() < math::powi(2.0, 128) {
+            Some(size)
+        } else {
+            None
+        }
+    }
+
+    /// Create a `Size` from a number of `bytes`, using the largest decimal
+    /// multiple that divides `bytes` evenly, e.g. 1_000_000 bytes becomes
+    /// "1 MB", but 1536 bytes stays "1536 B". See [`from_bytes_binary`] to use
+    /// binary multiples.
+    ///
+    /// Note that values above 2^53 can't be represented exactly, such large
+    /// values are rounded to the nearest representable value.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Size;
+    /// println!("size: {}", Size::from_bytes(1_000_000)); // 1 MB
+    /// println!("size: {}", Size::from_bytes(1536)); // 1536 B
+    /// # }
+    /// ```
+    ///
+    /// [`from_bytes_binary`]: #method.from_bytes_binary
+    pub fn from_bytes(bytes: u128) -> Size {
+        Size::from_bytes_using(bytes, Convention::Decimal)
+    }
+
+    /// Same as [`from_bytes`], but using binary multiples, e.g. 1_073_741_824
+    /// bytes becomes "1 GiB".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Size;
+    /// println!("size: {}", Size::from_bytes_binary(1_073_741_824)); // 1 GiB
+    /// println!("size: {}", Size::from_bytes_binary(1536)); // 1536 B
+    /// # }
+    /// ```
+    ///
+    /// [`from_bytes`]: #method.from_bytes
+    pub fn from_bytes_binary(bytes: u128) -> Size {
+        Size::from_bytes_using(bytes, Convention::Binary)
+    }
+
+    /// Create a `Size` from a (fractional) number of bytes, e.g. an average
+    /// file size. Returns an error if `bytes` is negative, or not a valid
+    /// number, i.e. NaN, infinite or [not normal].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use std::f64;
+    /// use human_size::{Size, ConversionError};
+    ///
+    /// let size = Size::try_from_f64_bytes(1234.5).unwrap();
+    /// println!("size: {}", size); // 1234.5 B
+    ///
+    /// let size = Size::try_from_f64_bytes(f64::NAN);
+    /// assert_eq!(size, Err(ConversionError::InvalidValue));
+    /// # }
+    /// ```
+    ///
+    /// [not normal]: https://doc.rust-lang.org/nightly/std/primitive.f64.html#method.is_normal
+    pub fn try_from_f64_bytes(bytes: f64) -> Result<Size, ConversionError> {
+        if bytes < 0.0 {
+            Err(ConversionError::Negative)
+        } else {
+            Size::new(bytes, Multiple::Byte)
+                .map_err(|()| ConversionError::InvalidValue)
+        }
+    }
+
     /// Convert the `Size` into bytes, be wary of overflows!
     ///
     /// ```
@@ -112,136 +375,2762 @@ impl Size {
     pub fn into_bytes(self) -> f64 {
         self.value * (self.multiple.multiple_of_bytes() as f64)
     }
-}
-
-impl FromStr for Size {
-    type Err = ParsingError;
-
-    fn from_str(input: &str) -> Result<Size, Self::Err> {
-        let (index, _) = input
-            .char_indices()
-            .find(|&(_, c)| !(c.is_numeric() || c == '.'))
-            .ok_or(ParsingError::MissingMultiple)?;
-        let value_part = &input[0..index];
-        if value_part.len() == 0 {
-            return Err(ParsingError::MissingValue);
-        }
-        let multiple_part = input[index..].trim();
-        let value = value_part.parse::<f64>().or_else(
-            |_| Err(ParsingError::InvalidValue),
-        )?;
-        let multiple = multiple_part.parse()?;
-
-        let size = Size::new(value, multiple).map_err(
-            |_| ParsingError::InvalidValue,
-        )?;
-        Ok(size)
-    }
-}
-
-impl Eq for Size {}
 
-impl PartialEq for Size {
-    fn eq(&self, other: &Size) -> bool {
-        self.into_bytes() == other.into_bytes()
+    /// Parse a `Size` from the start of `input`, returning the `Size` and the
+    /// remainder of `input` that comes after the multiple. This can be used
+    /// to parse sizes embedded in larger strings, e.g. "10MB/s".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let (size, remainder) = Size::parse_prefix("10MB/s").unwrap();
+    /// assert_eq!(size, Size::new(10, Multiple::Megabyte).unwrap());
+    /// assert_eq!(remainder, "/s");
+    /// # }
+    /// ```
+    pub fn parse_prefix(input: &str) -> Result<(Size, &str), ParsingError> {
+        Size::parse_prefix_with(input, &ParseOptions::new())
     }
-}
 
-impl PartialOrd for Size {
-    fn partial_cmp(&self, other: &Size) -> Option<Ordering> {
-        self.into_bytes().partial_cmp(&other.into_bytes())
+    /// Parse a `Size` using `options`, see [`ParseOptions`] for the available
+    /// options.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, ParseOptions};
+    /// let options = ParseOptions::new().uppercase_kilo(true);
+    /// let size = Size::parse_with("1 KB", &options).unwrap();
+    /// assert_eq!(size, Size::new(1, Multiple::Kilobyte).unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`ParseOptions`]: struct.ParseOptions.html
+    pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Size, ParsingError> {
+        Size::parse_tokens(input, options).map_err(|(kind, _)| kind)
     }
-}
 
-impl fmt::Display for Size {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {}", self.value, self.multiple)
+    /// Parse a `Size` like [`FromStr`] does, but on error also report which
+    /// part of `input` caused it, e.g. to underline it in a command line
+    /// interface. See [`SpannedError`].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, ParsingError};
+    /// let err = Size::parse_spanned("5 XB").unwrap_err();
+    /// assert_eq!(err.kind(), ParsingError::InvalidMultiple);
+    /// assert_eq!(err.token(), "XB");
+    /// assert_eq!(err.position(), 2);
+    /// # }
+    /// ```
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+    /// [`SpannedError`]: struct.SpannedError.html
+    pub fn parse_spanned<'a>(input: &'a str) -> Result<Size, SpannedError<'a>> {
+        Size::parse_tokens(input, &ParseOptions::new())
+            .map_err(|(kind, token)| SpannedError::new(kind, input, token))
     }
-}
 
-/// A `Multiple` represent a multiple of bytes. This is mainly used to keep track
-/// of what multiple [`Size`] uses, so it can display it using the same multiple
-/// of bytes.
-///
-/// [`Size`]: struct.Size.html
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum Multiple {
-    /// Represents a single byte, value * 1, "B" when parsing text.
-    Byte,
+    /// Parse a `Size` only accepting the exact format used by the [`Display`]
+    /// implementation, e.g. "10 MB": the value as displayed by Rust, a single
+    /// space and the canonical symbol of the multiple. This means that any
+    /// accepted input displays identically, which is useful for strict
+    /// machine to machine formats.
+    ///
+    /// Lenient forms accepted by [`FromStr`] are rejected, such as "10MB"
+    /// (without space), "10.0 MB", "10 KB" (as `Kibibyte`), or "16 bit". Like
+    /// [`FromStr`] signed values, e.g. "-1 B", are rejected as well.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, ParsingError};
+    /// let size = Size::parse_exact("10 MB").unwrap();
+    /// assert_eq!(size, Size::new(10, Multiple::Megabyte).unwrap());
+    ///
+    /// assert_eq!(Size::parse_exact("10 KB"), Err(ParsingError::InvalidMultiple));
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/nightly/core/fmt/trait.Display.html
+    /// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+    pub fn parse_exact(input: &str) -> Result<Size, ParsingError> {
+        if input.is_empty() {
+            return Err(ParsingError::EmptyInput);
+        }
 
-    /// A kilobyte, value * 1,000 (1000^1), "kB" in when parsing from text.
-    Kilobyte,
+        let index = input.find(' ').unwrap_or(input.len());
+        let (value_part, multiple_part) = input.split_at(index);
+        if value_part.is_empty() {
+            return Err(ParsingError::MissingValue);
+        }
+        // Sizes are never signed.
+        if value_part.starts_with(&['-', '+'][..]) {
+            return Err(ParsingError::InvalidValue);
+        }
+        let value = value_part.parse::<f64>()
+            .ok()
+            .filter(|value| {
+                let mut equals = writers::Equals::new(value_part);
+                write!(equals, "{}", value).is_ok() && equals.is_equal()
+            })
+            .ok_or(ParsingError::InvalidValue)?;
 
-    /// A megabyte, value * 1,000,000 (1000^2), "MB" in when parsing from text.
-    Megabyte,
+        let multiple_part = match multiple_part.strip_prefix(' ') {
+            Some("") | None => return Err(ParsingError::MissingMultiple),
+            Some(multiple_part) => multiple_part,
+        };
+        let multiple = multiple_part.parse::<Multiple>()
+            .ok()
+            .filter(|multiple| multiple.symbol() == multiple_part)
+            .ok_or(ParsingError::InvalidMultiple)?;
 
-    /// A gigabyte, value * 1,000,000,000 (1000^3), "GB" in when parsing from
-    /// text.
-    Gigabyte,
+        Size::new(value, multiple).map_err(|()| ParsingError::InvalidValue)
+    }
 
-    /// A terabyte, value * 1,000,000,000,000 (1000^4), "TB" in when parsing
-    /// from text.
-    Terabyte,
+    /// Parse a `Size`, resolving ambiguous multiples using `convention`. The
+    /// only ambiguous multiple is "KB", which is parsed as `Kilobyte` using
+    /// the decimal convention and as `Kibibyte` using the binary convention.
+    /// Unambiguous multiples, e.g. "kB" or "MiB", are parsed as usual.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Convention};
+    /// let size = Size::parse_with_context("10 KB", Convention::Decimal).unwrap();
+    /// assert_eq!(size, Size::new(10, Multiple::Kilobyte).unwrap());
+    /// let size = Size::parse_with_context("10 KB", Convention::Binary).unwrap();
+    /// assert_eq!(size, Size::new(10, Multiple::Kibibyte).unwrap());
+    /// # }
+    /// ```
+    pub fn parse_with_context(input: &str, convention: Convention) -> Result<Size, ParsingError> {
+        let options = ParseOptions::new()
+            .uppercase_kilo(convention == Convention::Decimal);
+        Size::parse_with(input, &options)
+    }
 
-    /// A petabyte, value * 1,000,000,000,000,000 (1000^5), "PB" in when
-    /// parsing from text.
-    Petabyte,
+    /// Parse a `Size` from a "key=value" string, e.g. "maxsize=10MB", by
+    /// parsing the part after the last `=`. If `input` doesn't contain a `=`
+    /// the entire `input` is parsed.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::parse_after_eq("maxsize=10MB").unwrap();
+    /// assert_eq!(size, Size::new(10, Multiple::Megabyte).unwrap());
+    /// # }
+    /// ```
+    pub fn parse_after_eq(input: &str) -> Result<Size, ParsingError> {
+        match input.rfind('=') {
+            Some(index) => input[index + 1..].parse(),
+            None => input.parse(),
+        }
+    }
 
-    /*
-    /// A exabyte, value * 1,000,000,000,000,000,000 (1000^6), "EB" in when
-    /// parsing from text.
-    Exabyte,
+    /// Parse a `Size` from a string that may only contain printable ASCII
+    /// characters, i.e. no control characters (including tabs) or non-ASCII
+    /// characters such as Unicode digits or look-alike letters. This is
+    /// useful when parsing untrusted input.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, ParsingError};
+    /// let size = Size::from_ascii_str("10 MB").unwrap();
+    /// assert_eq!(size, Size::new(10, Multiple::Megabyte).unwrap());
+    ///
+    /// // Uses a Cyrillic "М".
+    /// assert_eq!(Size::from_ascii_str("10 \u{41C}B"), Err(ParsingError::NonAscii));
+    /// # }
+    /// ```
+    pub fn from_ascii_str(input: &str) -> Result<Size, ParsingError> {
+        if input.bytes().any(|b| !b.is_ascii() || b.is_ascii_control()) {
+            return Err(ParsingError::NonAscii);
+        }
+        input.parse()
+    }
 
-    /// A zettabyte, value * 1,000,000,000,000,000,000,000 (1000^7), "ZB" in
-    /// when parsing from text.
-    Zettabyte,
+    /// Same as [`parse_prefix`], but using `options`.
+    ///
+    /// [`parse_prefix`]: #method.parse_prefix
+    fn parse_prefix_with<'a>(input: &'a str, options: &ParseOptions) -> Result<(Size, &'a str), ParsingError> {
+        Size::parse_prefix_tokens(input, options).map_err(|(kind, _)| kind)
+    }
 
-    /// A yottabyte, value * 1,000,000,000,000,000,000,000,000 (1000^8), "YB"
-    /// in when parsing from text.
-    Yottabyte,
-    */
+    /// Parse an entire `Size` from `input`, see [`parse_prefix_tokens`].
+    ///
+    /// [`parse_prefix_tokens`]: #method.parse_prefix_tokens
+    fn parse_tokens<'a>(input: &'a str, options: &ParseOptions) -> Result<Size, (ParsingError, &'a str)> {
+        let (size, remainder) = Size::parse_prefix_tokens(input, options)?;
+        let extra = remainder.trim();
+        if extra.is_empty() {
+            Ok(size)
+        } else {
+            Err((ParsingError::UnknownExtra, extra))
+        }
+    }
 
-    /// A kibibyte, value * 1,024 (1024^1), "KiB" or "KB" in when parsing from
-    /// text.
-    Kibibyte,
+    /// Parse a `Size` from the start of `input`, splitting it into the sign,
+    /// value and multiple tokens. On error the offending token is returned,
+    /// always as slice of `input` so its position can be determined. If the
+    /// token is missing it's an empty slice at the position it was expected.
+    fn parse_prefix_tokens<'a>(input: &'a str, options: &ParseOptions) -> Result<(Size, &'a str), (ParsingError, &'a str)> {
+        let (value, value_part, remainder) = Size::parse_value_tokens(input)?;
+        Size::parse_multiple_tokens(value, value_part, remainder, options)
+    }
 
-    /// A mebibyte, value * 1,048,576 (1024^2), "MiB" in when parsing from text.
-    Mebibyte,
+    /// Parse the value from the start of `input`, returning the value, the
+    /// token of the value and the remainder of `input`. This is shared by
+    /// the parsers of all types in the crate, e.g. [`Bits`], so they all
+    /// accept the same values, including underscores and exponents.
+    ///
+    /// [`Bits`]: struct.Bits.html
+    fn parse_value_tokens(input: &str) -> Result<(f64, &str, &str), (ParsingError, &str)> {
+        let input = input.trim_start();
+        if input.is_empty() {
+            return Err((ParsingError::EmptyInput, input));
+        }
 
-    /// A gigibyte, value * 1,073,741,824 (1024^3), "GiB" in when parsing from
-    /// text.
-    Gigibyte,
+        // Sizes are never signed, but the sign is tokenized so it can be
+        // reported as part of the invalid value.
+        let sign_len = if input.starts_with(['+', '-']) { 1 } else { 0 };
+        let index = input[sign_len..]
+            .find(|c: char| !(c.is_numeric() || c == '.' || c == '_'))
+            .map_or(input.len(), |index| sign_len + index);
+        let index = if index > sign_len { Size::exponent_end(input, index) } else { index };
+        let (value_part, remainder) = input.split_at(index);
+        if sign_len != 0 {
+            return Err((ParsingError::InvalidValue, value_part));
+        } else if remainder.is_empty() {
+            return Err((ParsingError::MissingMultiple, remainder));
+        } else if value_part.is_empty() {
+            return Err((ParsingError::MissingValue, value_part));
+        }
+        let value = Size::parse_value(value_part)
+            .ok_or((ParsingError::InvalidValue, value_part))?;
+        Ok((value, value_part, remainder))
+    }
 
-    /// A tebibyte, value * 1,099,511,627,776 (1024^4), "TiB" in when parsing
-    /// from text.
-    Tebibyte,
+    /// If `input` contains an exponent at `index`, e.g. "e3" or "E-2",
+    /// returns the index of the end of the exponent, otherwise returns
+    /// `index`. An "e" without digits is not an exponent, but (part of) the
+    /// multiple.
+    fn exponent_end(input: &str, index: usize) -> usize {
+        let bytes = input.as_bytes();
+        if !matches!(bytes.get(index), Some(b'e') | Some(b'E')) {
+            return index;
+        }
+        let mut end = index + 1;
+        if matches!(bytes.get(end), Some(b'+') | Some(b'-')) {
+            end += 1;
+        }
+        let digits = bytes[end..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            index
+        } else {
+            end + digits
+        }
+    }
 
-    /// A pebibyte, value * 1,125,899,906,842,624 (1024^5), "PiB" in when
-    /// parsing from text.
-    Pebibyte,
+    /// Parse `input` as value, allowing underscores between digits as
+    /// separators, e.g. "1_000". Unlike in Rust literals underscores may not
+    /// be repeated or trail the value.
+    fn parse_value(input: &str) -> Option<f64> {
+        if !input.contains('_') {
+            return input.parse().ok();
+        }
 
-    /*
-    /// A exbibyte, value * 1,152,921,504,606,846,976 (1024^6), "EiB" in when
-    /// parsing from text.
-    Exbibyte,
+        let bytes = input.as_bytes();
+        let is_digit = |index: Option<usize>| {
+            index.and_then(|index| bytes.get(index)).map_or(false, u8::is_ascii_digit)
+        };
+        // The value is copied without the underscores into a buffer to avoid
+        // allocating. Longer values are not accepted, but values using
+        // separators are written by humans so they'll fit.
+        let mut buf = [0; 128];
+        let mut len = 0;
+        for (index, &b) in bytes.iter().enumerate() {
+            if b == b'_' {
+                if !(is_digit(index.checked_sub(1)) && is_digit(Some(index + 1))) {
+                    return None;
+                }
+            } else {
+                *buf.get_mut(len)? = b;
+                len += 1;
+            }
+        }
+        str::from_utf8(&buf[..len]).ok()?.parse().ok()
+    }
 
-    /// A zebibyte, value * 1,180,591,620,717,411,303,424 (1024^7), "ZiB" in
-    /// when parsing from text.
-    Zebibyte,
+    /// Parse the multiple from the start of `input`, returning a `Size` with
+    /// `value` and the remainder of `input`. `value_part` is the token of the
+    /// value, used if `value` turns out to be invalid.
+    fn parse_multiple_tokens<'a>(value: f64, value_part: &'a str, input: &'a str, options: &ParseOptions) -> Result<(Size, &'a str), (ParsingError, &'a str)> {
+        let input = input.trim_start();
+        let index = input.find(|c: char| !c.is_alphabetic())
+            .unwrap_or(input.len());
+        let (multiple_part, remainder) = input.split_at(index);
+        if multiple_part.is_empty() {
+            return Err((ParsingError::MissingMultiple, multiple_part));
+        }
 
-    /// A yobibyte, value * 1,208,925,819,614,629,174,706,176 (1024^8), "YiB"
+        // Bits aren't a multiple of bytes, so they're converted into
+        // (fractional) bytes here.
+        let size = if multiple_part == "bit" || multiple_part == "bits" {
+            Size::new(value / 8.0, Multiple::Byte)
+        } else {
+            let multiple = options.parse_multiple(multiple_part)
+                .map_err(|kind| (kind, multiple_part))?;
+            Size::new(value, multiple)
+        };
+        size.map(|size| (size, remainder))
+            .map_err(|()| (ParsingError::InvalidValue, value_part))
+    }
+
+    /// Parse a `Size` written in a localized format, using the decimal and
+    /// group separators from `locale`. See [`LocaleOptions`] for more.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, LocaleOptions};
+    /// let locale = LocaleOptions::new().decimal_separator(',').group_separator('.');
+    /// let size = Size::parse_localized("1.234,5 MB", &locale).unwrap();
+    /// assert_eq!(size, Size::new(1234.5, Multiple::Megabyte).unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`LocaleOptions`]: struct.LocaleOptions.html
+    #[cfg(feature = "alloc")]
+    pub fn parse_localized(input: &str, locale: &LocaleOptions) -> Result<Size, ParsingError> {
+        if input.trim().is_empty() {
+            return Err(ParsingError::EmptyInput);
+        }
+        let (number, remainder) = locale.normalize_number(input)?;
+        if number.is_empty() {
+            return Err(ParsingError::MissingValue);
+        }
+        let value = number.parse::<f64>().map_err(
+            |_| ParsingError::InvalidValue,
+        )?;
+        let value_part = &input[..input.len() - remainder.len()];
+        let (size, remainder) = Size::parse_multiple_tokens(value, value_part, remainder, &ParseOptions::new())
+            .map_err(|(kind, _)| kind)?;
+        if remainder.trim().is_empty() {
+            Ok(size)
+        } else {
+            Err(ParsingError::UnknownExtra)
+        }
+    }
+    /// Parse the size from a line of output of the Windows `dir` command,
+    /// e.g. `"1,048,576 bytes"` or the summary line
+    /// `"   3 File(s)      1,234,567 bytes"`. The number directly before the
+    /// word "bytes" (case insensitive) is used, it may use commas to group
+    /// thousands. Returns `None` if no such number is found.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let line = "               2 Dir(s)  12,345,678,900 bytes free";
+    /// let size = Size::from_windows_dir(line).unwrap();
+    /// assert_eq!(size, Size::new(12_345_678_900.0, Multiple::Byte).unwrap());
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn from_windows_dir(line: &str) -> Option<Size> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let index = words.iter()
+            .position(|word| word.eq_ignore_ascii_case("bytes"))?;
+        let number = words[..index].last()?;
+        if !number.chars().all(|c| c.is_ascii_digit() || c == ',') {
+            return None;
+        }
+        let locale = LocaleOptions::new().group_separator(',');
+        Size::parse_localized(&format!("{} B", number), &locale).ok()
+    }
+
+    /// Set the preferred [`Convention`] of the `Size`. This convention is used
+    /// when the `Size` is humanized, see [`humanize`]. Without it the
+    /// convention is inferred from the multiple of the `Size`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Convention};
+    /// let size = Size::new(1_048_576, Multiple::Byte).unwrap();
+    /// println!("size: {}", size.humanize()); // 1.048576 MB
+    ///
+    /// let size = size.with_convention(Convention::Binary);
+    /// println!("size: {}", size.humanize()); // 1 MiB
+    /// # }
+    /// ```
+    ///
+    /// [`Convention`]: enum.Convention.html
+    /// [`humanize`]: #method.humanize
+    pub fn with_convention(mut self, convention: Convention) -> Size {
+        self.convention = Some(convention);
+        self
+    }
+
+    /// Express the `Size` in the largest multiple of bytes in which the value
+    /// is still at least one, e.g. "1500 kB" becomes "1.5 MB". Which multiples
+    /// are considered depends on the convention set using
+    /// [`with_convention`], or if not set the family of the current multiple,
+    /// where `Byte` is considered decimal. The returned `Size` keeps the
+    /// preferred convention. Sizes smaller than one kilobyte (or kibibyte)
+    /// are expressed in bytes, e.g. "3 B" rather than "0.003 kB".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1500, Multiple::Kilobyte).unwrap();
+    /// println!("size: {}", size.humanize()); // 1.5 MB
+    ///
+    /// let size = Size::new(2048, Multiple::Kibibyte).unwrap();
+    /// println!("size: {}", size.humanize()); // 2 MiB
+    /// # }
+    /// ```
+    ///
+    /// [`with_convention`]: #method.with_convention
+    pub fn humanize(&self) -> Size {
+        self.humanize_using(self.convention(), u64::MAX)
+    }
+
+    /// Same as [`humanize`], but never uses a multiple larger than
+    /// `max_multiple` and always uses multiples of `convention`. This allows
+    /// large sizes to be shown in a consistent multiple, e.g. "4096 GiB"
+    /// rather than "4 TiB".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Convention};
+    /// let size = Size::new(4, Multiple::Tebibyte).unwrap();
+    /// let size = size.humanize_capped(Multiple::Gigibyte, Convention::Binary);
+    /// println!("size: {}", size); // 4096 GiB
+    /// # }
+    /// ```
+    ///
+    /// [`humanize`]: #method.humanize
+    pub fn humanize_capped(&self, max_multiple: Multiple, convention: Convention) -> Size {
+        self.humanize_using(convention, max_multiple.multiple_of_bytes())
+    }
+
+    /// Humanize the size using the multiples of `convention`, that are at
+    /// most `max_bytes` in size.
+    fn humanize_using(&self, convention: Convention, max_bytes: u64) -> Size {
+        let bytes = math::abs(self.into_bytes());
+        let multiple = convention.multiples().iter().rev()
+            .filter(|multiple| multiple.multiple_of_bytes() <= max_bytes)
+            .find(|multiple| bytes >= multiple.multiple_of_bytes() as f64)
+            .cloned()
+            .unwrap_or(Multiple::Byte);
+        Size {
+            value: self.value_in(multiple),
+            multiple,
+            convention: self.convention,
+        }
+    }
+
+    /// Re-express the `Size` in the next larger multiple of the same family,
+    /// e.g. "1000 kB" becomes "1 MB". Returns `None` if there is no larger
+    /// multiple or if the value would become fractional. For `Byte` the
+    /// preferred convention, see [`with_convention`], decides the family.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(2048, Multiple::Kibibyte).unwrap();
+    /// println!("size: {}", size.shift_up().unwrap()); // 2 MiB
+    ///
+    /// let size = Size::new(1500, Multiple::Kilobyte).unwrap();
+    /// println!("size: {:?}", size.shift_up()); // None, 1.5 MB is fractional.
+    /// # }
+    /// ```
+    ///
+    /// [`with_convention`]: #method.with_convention
+    pub fn shift_up(&self) -> Option<Size> {
+        let multiple = match self.multiple {
+            Multiple::Byte => self.convention().multiples()[1],
+            multiple => multiple.next_larger()?,
+        };
+        let value = self.value_in(multiple);
+        if math::fract(value) != 0.0 {
+            return None;
+        }
+        Some(Size { value, multiple, convention: self.convention })
+    }
+
+    /// Re-express the `Size` in the next smaller multiple of the same family,
+    /// e.g. "1 MiB" becomes "1024 KiB". Returns `None` if there is no smaller
+    /// multiple or if the value would overflow.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1.5, Multiple::Megabyte).unwrap();
+    /// println!("size: {}", size.shift_down().unwrap()); // 1500 kB
+    /// # }
+    /// ```
+    pub fn shift_down(&self) -> Option<Size> {
+        let multiple = self.multiple.next_smaller()?;
+        let value = self.value_in(multiple);
+        if !value.is_finite() {
+            return None;
+        }
+        Some(Size { value, multiple, convention: self.convention })
+    }
+
+    /// Parse a `Size` leniently, first removing a leading UTF-8 byte order mark
+    /// and any surrounding (Unicode) whitespace, such as non-breaking spaces.
+    /// This is useful when parsing sizes read from files.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::parse_bom_tolerant("\u{FEFF}100 kB\u{A0}").unwrap();
+    /// assert_eq!(size, Size::new(100, Multiple::Kilobyte).unwrap());
+    /// # }
+    /// ```
+    pub fn parse_bom_tolerant(input: &str) -> Result<Size, ParsingError> {
+        let input = input.trim();
+        let input = input.trim_start_matches('\u{FEFF}');
+        input.trim().parse()
+    }
+
+    /// Returns the number of `item`s that fit in this `Size`, e.g. how many
+    /// 4 KiB blocks fit in 1 GiB. Any remainder is discarded. Returns `None`
+    /// if `item` is zero bytes, or if the count is not finite or doesn't fit
+    /// in an `u128`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let disk = Size::new(1, Multiple::Gigibyte).unwrap();
+    /// let block = Size::new(4, Multiple::Kibibyte).unwrap();
+    /// println!("blocks: {:?}", disk.count_fitting(block)); // Some(262144)
+    /// # }
+    /// ```
+    pub fn count_fitting(&self, item: Size) -> Option<u128> {
+        let item_bytes = item.into_bytes();
+        if item_bytes == 0.0 {
+            return None;
+        }
+        let count = math::floor(self.into_bytes() / item_bytes);
+        if !count.is_finite() || count >= u128::MAX as f64 {
+            None
+        } else {
+            Some(count as u128)
+        }
+    }
+
+    /// Display the `Size` in `multiple`, rounding the value up to a whole
+    /// number. This is useful for conservatively displaying required
+    /// capacity, e.g. "you need at least 2 GiB of space".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1.1, Multiple::Gigibyte).unwrap();
+    /// println!("need: {}", size.display_rounded_up(Multiple::Gigibyte)); // 2 GiB
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn display_rounded_up(&self, multiple: Multiple) -> String {
+        format!("{} {}", math::ceil(self.value_in(multiple)), multiple)
+    }
+
+    /// Convert the `Size` into a number of bytes as integer `T`, e.g. `u16`
+    /// or `u64`. Fractional bytes are truncated. Returns an error if the
+    /// `Size` is negative or if the number of bytes doesn't fit in `T`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, ConversionError};
+    /// let size = Size::new(100, Multiple::Kilobyte).unwrap();
+    /// assert_eq!(size.to_integer::<u32>(), Ok(100_000));
+    /// assert_eq!(size.to_integer::<u16>(), Err(ConversionError::Overflow));
+    /// # }
+    /// ```
+    pub fn to_integer<T>(&self) -> Result<T, ConversionError>
+        where T: TryFrom<u128>,
+    {
+        let bytes = self.into_bytes();
+        if bytes < 0.0 {
+            Err(ConversionError::Negative)
+        } else if bytes >= math::powi(2.0, 128) {
+            Err(ConversionError::Overflow)
+        } else {
+            T::try_from(bytes as u128).map_err(|_| ConversionError::Overflow)
+        }
+    }
+
+    /// Format the `Size` using `options`. Unlike the [`Display`]
+    /// implementation this limits the number of decimals shown, which is
+    /// most useful when combined with [`humanize`].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, FormatOptions};
+    /// let size = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// let size = Size::new(size.into_bytes() / 3.0, Multiple::Byte).unwrap();
+    /// let formatted = size.humanize().format(&FormatOptions::new());
+    /// assert_eq!(formatted, "333.333333 MB");
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/nightly/core/fmt/trait.Display.html
+    /// [`humanize`]: #method.humanize
+    #[cfg(feature = "alloc")]
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let mut output = String::new();
+        let _ = self.write_with(&mut output, options);
+        output
+    }
+
+    /// Format the `Size` expressed in `multiple`, regardless of the multiple
+    /// of the `Size` itself, e.g. to show all sizes in a table column in
+    /// MiB. The value may be fractional, it's formatted using the default
+    /// [`FormatOptions`]. See [`convert`] to get the converted `Size`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// assert_eq!(size.format_in(Multiple::Megabyte), "1000 MB");
+    /// let size = Size::new(512, Multiple::Mebibyte).unwrap();
+    /// assert_eq!(size.format_in(Multiple::Gigibyte), "0.5 GiB");
+    /// # }
+    /// ```
+    ///
+    /// [`FormatOptions`]: struct.FormatOptions.html
+    /// [`convert`]: #method.convert
+    #[cfg(feature = "alloc")]
+    pub fn format_in(&self, multiple: Multiple) -> String {
+        let size = Size { value: self.value_in(multiple), multiple, convention: self.convention };
+        size.format(&FormatOptions::new())
+    }
+
+    /// Write the `Size` into `writer` formatted using `options`, see
+    /// [`format`].
+    ///
+    /// [`format`]: #method.format
+    fn write_with<W: Write + ?Sized>(&self, writer: &mut W, options: &FormatOptions) -> fmt::Result {
+        options.write_value(writer, self.value)?;
+        write!(writer, " {}", options.symbol(self.multiple))
+    }
+
+    /// Returns the number of `block`s this `Size` occupies, rounding up, and
+    /// the space wasted in the last, partially used, block. The wasted space
+    /// is expressed in the multiple of `block`. The computation is done on
+    /// whole bytes using integers, so the result is exact for any size.
+    ///
+    /// Returns `None` if `block` is zero bytes, if either size is negative,
+    /// has fractional bytes or doesn't fit in an `u128`, or if the number of
+    /// blocks doesn't fit in an `u64`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let file = Size::new(10, Multiple::Kibibyte).unwrap();
+    /// let block = Size::new(4, Multiple::Kibibyte).unwrap();
+    /// let (blocks, wasted) = file.allocation(block).unwrap();
+    /// println!("uses {} blocks, {} wasted", blocks, wasted); // uses 3 blocks, 2 KiB wasted
+    /// # }
+    /// ```
+    pub fn allocation(&self, block: Size) -> Option<(u64, Size)> {
+        let block_bytes = block.whole_bytes()?;
+        if block_bytes == 0 {
+            return None;
+        }
+        let bytes = self.whole_bytes()?;
+        let remainder = bytes % block_bytes;
+        let (blocks, wasted) = if remainder == 0 {
+            (bytes / block_bytes, 0)
+        } else {
+            (bytes / block_bytes + 1, block_bytes - remainder)
+        };
+        let blocks = u64::try_from(blocks).ok()?;
+        let wasted = wasted as f64 / block.multiple.multiple_of_bytes() as f64;
+        Some((blocks, Size {
+            value: wasted,
+            multiple: block.multiple,
+            convention: None,
+        }))
+    }
+
+    /// The size in bytes as `u128`, or `None` if the size is negative, has
+    /// fractional bytes or doesn't fit.
+    fn whole_bytes(&self) -> Option<u128> {
+        if math::fract(self.into_bytes()) != 0.0 {
+            None
+        } else {
+            self.to_integer().ok()
+        }
+    }
+
+    /// Decompose the `Size` into whole multiples of `convention`, from large
+    /// to small, e.g. "1234567 B" becomes "1 MB", "234 kB" and "567 B". Any
+    /// fractional bytes are kept in the last, `Byte`, part. A zero size is
+    /// decomposed into "0 B". See [`fold_units`] for the inverse.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Convention};
+    /// let size = Size::new(1.5, Multiple::Gigibyte).unwrap();
+    /// let parts = size.decompose(Convention::Binary);
+    /// println!("parts: {:?}", parts); // 1 GiB, 512 MiB
+    /// # }
+    /// ```
+    ///
+    /// [`fold_units`]: #method.fold_units
+    #[cfg(feature = "alloc")]
+    pub fn decompose(&self, convention: Convention) -> Vec<Size> {
+        let mut bytes = self.into_bytes();
+        let mut parts = Vec::new();
+        for &multiple in convention.multiples()[1..].iter().rev() {
+            let multiple_bytes = multiple.multiple_of_bytes() as f64;
+            let value = math::trunc(bytes / multiple_bytes);
+            if value != 0.0 {
+                parts.push(Size { value, multiple, convention: None });
+                bytes -= value * multiple_bytes;
+            }
+        }
+        if bytes != 0.0 || parts.is_empty() {
+            parts.push(Size { value: bytes, multiple: Multiple::Byte, convention: None });
+        }
+        parts
+    }
+
+    /// Sum `parts` into a single, humanized, `Size`. The convention of the
+    /// first part is used to humanize the result. An empty slice results in
+    /// zero bytes. This is the inverse of [`decompose`].
+    ///
+    /// Returns an error if the sum overflows.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let parts = [
+    ///     Size::new(1, Multiple::Gigibyte).unwrap(),
+    ///     Size::new(512, Multiple::Mebibyte).unwrap(),
+    /// ];
+    /// let size = Size::fold_units(&parts).unwrap();
+    /// println!("size: {}", size); // 1.5 GiB
+    /// # }
+    /// ```
+    ///
+    /// [`decompose`]: #method.decompose
+    pub fn fold_units(parts: &[Size]) -> Result<Size, ConversionError> {
+        // Summing floats starts at negative zero, so an empty slice would
+        // result in "-0 B", start at (positive) zero instead.
+        let bytes = parts.iter().fold(0.0, |bytes, part| bytes + part.into_bytes());
+        if !bytes.is_finite() {
+            return Err(ConversionError::Overflow);
+        }
+        let first = parts.first().cloned().unwrap_or(Size {
+            value: 0.0,
+            multiple: Multiple::Byte,
+            convention: None,
+        });
+        let size = Size {
+            value: bytes / first.multiple.multiple_of_bytes() as f64,
+            multiple: first.multiple,
+            convention: first.convention,
+        };
+        Ok(size.humanize())
+    }
+
+    /// Put the `Size` in perspective by comparing it to a number of labeled
+    /// reference sizes, e.g. a CD or DVD. Returns the label of each reference
+    /// along with the size as fraction of that reference.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let references = [
+    ///     ("CD", Size::new(700, Multiple::Megabyte).unwrap()),
+    ///     ("DVD", Size::new(4.7, Multiple::Gigabyte).unwrap()),
+    /// ];
+    /// let size = Size::new(1.4, Multiple::Gigabyte).unwrap();
+    /// for (label, fraction) in size.describe_vs_references(&references) {
+    ///     println!("{:.2} {}s", fraction, label); // 2.00 CDs, 0.30 DVDs
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn describe_vs_references<'a>(&self, references: &[(&'a str, Size)]) -> Vec<(&'a str, f64)> {
+        let bytes = self.into_bytes();
+        references.iter()
+            .map(|&(label, reference)| (label, bytes / reference.into_bytes()))
+            .collect()
+    }
+
+    /// Re-express the `Size` in the largest multiple, equal to or smaller than
+    /// the current multiple, in which the value is a whole number, e.g.
+    /// "0.5 GiB" becomes "512 MiB". If no such multiple exists the size is
+    /// expressed in bytes.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(0.5, Multiple::Gigibyte).unwrap();
+    /// println!("size: {}", size.widen_until_integer()); // 512 MiB
+    /// # }
+    /// ```
+    pub fn widen_until_integer(&self) -> Size {
+        let mut multiple = self.multiple;
+        loop {
+            let value = self.value_in(multiple);
+            match multiple.next_smaller() {
+                Some(smaller) if math::fract(value) != 0.0 => multiple = smaller,
+                _ => return Size { value, multiple, convention: self.convention },
+            }
+        }
+    }
+
+    /// Convert the `Size` into the `target` multiple, e.g. to always show sizes
+    /// in MiB. The value may be fractional, "1500 kB" converted into megabytes
+    /// is "1.5 MB". Returns `None` if the value doesn't fit in the target
+    /// multiple, which can only happen when converting into a smaller
+    /// multiple.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// println!("size: {}", size.convert(Multiple::Megabyte).unwrap()); // 1000 MB
+    /// # }
+    /// ```
+    pub fn convert(&self, target: Multiple) -> Option<Size> {
+        let value = self.value_in(target);
+        if value.is_finite() {
+            Some(Size { value, multiple: target, convention: self.convention })
+        } else {
+            None
+        }
+    }
+
+    /// Simplify the multiple of the `Size`, moving to the largest multiple in
+    /// the same family, decimal or binary, in which the value is still a whole
+    /// number, e.g. "5000 kB" becomes "5 MB". Sizes that aren't a whole number
+    /// in the current multiple are first widened, see
+    /// [`widen_until_integer`]. Zero sizes keep their multiple.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(5000, Multiple::Kilobyte).unwrap();
+    /// println!("size: {}", size.normalize()); // 5 MB
+    /// # }
+    /// ```
+    ///
+    /// [`widen_until_integer`]: #method.widen_until_integer
+    pub fn normalize(&self) -> Size {
+        let mut size = self.widen_until_integer();
+        if size.value == 0.0 {
+            return size;
+        }
+        while let Some(larger) = size.multiple.next_larger() {
+            let value = size.value_in(larger);
+            if math::fract(value) != 0.0 {
+                break;
+            }
+            size = Size { value, multiple: larger, convention: size.convention };
+        }
+        size
+    }
+
+    /// Scale the `Size` by the ratio `numerator / denominator`, keeping the
+    /// multiple. The ratio is applied before converting into bytes, so large
+    /// sizes and numerators can be used without overflowing. Returns `None`
+    /// if `denominator` is zero or if the resulting value overflows, an
+    /// intermediate overflow of `value * numerator` doesn't fail.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(3, Multiple::Gigabyte).unwrap();
+    /// println!("size: {}", size.checked_scale_ratio(2, 3).unwrap()); // 2 GB
+    /// assert!(size.checked_scale_ratio(1, 0).is_none());
+    /// # }
+    /// ```
+    pub fn checked_scale_ratio(&self, numerator: u64, denominator: u64) -> Option<Size> {
+        if denominator == 0 {
+            return None;
+        }
+        let mut value = self.value * numerator as f64 / denominator as f64;
+        if !value.is_finite() {
+            // Multiplying first overflowed, divide first instead.
+            value = self.value / denominator as f64 * numerator as f64;
+        }
+        if value.is_finite() {
+            Some(Size { value, multiple: self.multiple, convention: self.convention })
+        } else {
+            None
+        }
+    }
+
+    /// Describe the `Size` using a coarse label, for displays where the
+    /// precise number doesn't matter. The size is first expressed in the
+    /// largest multiple of `convention` that is not larger than it (see
+    /// [`humanize`]), the value then decides the label:
+    ///
+    /// * less than the smallest multiple: `"< 1 kB"` (or `"< 1 KiB"`),
+    /// * below 10: `"a few MB"`,
+    /// * below 100: `"tens of MB"`,
+    /// * below 1000: `"hundreds of MB"`,
+    /// * otherwise: `"thousands of MB"`, which only happens for binary
+    ///   multiples between 1000 and 1024 and for the largest multiple.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Convention};
+    /// let size = Size::new(250, Multiple::Gigabyte).unwrap();
+    /// assert_eq!(size.approx_label(Convention::Decimal), "hundreds of GB");
+    /// # }
+    /// ```
+    ///
+    /// [`humanize`]: #method.humanize
+    #[cfg(feature = "alloc")]
+    pub fn approx_label(&self, convention: Convention) -> String {
+        let size = self.humanize_using(convention, u64::MAX);
+        if size.multiple == Multiple::Byte {
+            return format!("< 1 {}", convention.multiples()[1]);
+        }
+        let quantifier = match math::abs(size.value) {
+            value if value < 10.0 => "a few",
+            value if value < 100.0 => "tens of",
+            value if value < 1000.0 => "hundreds of",
+            _ => "thousands of",
+        };
+        format!("{} {}", quantifier, size.multiple)
+    }
+
+    /// Combine the `Size` with `other` using `op`, which receives both sizes
+    /// in (whole) bytes. The result of `op` is converted back into a `Size`
+    /// humanized using decimal multiples, see [`humanize`]. This allows byte-level math, e.g.
+    /// taking the maximum or the greatest common divisor, with the same
+    /// conversion and overflow handling in one place.
+    ///
+    /// Both sizes are converted like [`to_integer`], so an error is returned
+    /// if either is negative or too large for an `u128`. `op` itself must
+    /// not overflow, use checked or saturating arithmetic where needed.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use std::cmp;
+    /// use human_size::{Size, Multiple};
+    /// let size1 = Size::new(1, Multiple::Megabyte).unwrap();
+    /// let size2 = Size::new(800, Multiple::Kilobyte).unwrap();
+    /// let max = size1.combine_with(&size2, cmp::max).unwrap();
+    /// println!("max: {}", max); // 1 MB
+    /// # }
+    /// ```
+    ///
+    /// [`humanize`]: #method.humanize
+    /// [`to_integer`]: #method.to_integer
+    pub fn combine_with(&self, other: &Size, op: impl Fn(u128, u128) -> u128) -> Result<Size, ConversionError> {
+        let bytes = op(self.to_integer()?, other.to_integer()?);
+        Size::try_from_f64_bytes(bytes as f64).map(|size| size.humanize())
+    }
+
+    /// Coarsen the `Size`, rounding it down to a whole number of `to`. Unlike
+    /// the rounding done when formatting, see [`format`], this changes the
+    /// size itself, so the exact size can't be recovered, e.g. when reporting
+    /// file sizes in telemetry. The returned `Size` uses `to` as multiple.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1_234_567, Multiple::Byte).unwrap();
+    /// println!("size: {}", size.coarsen(Multiple::Kilobyte)); // 1234 kB
+    /// # }
+    /// ```
+    ///
+    /// [`format`]: #method.format
+    pub fn coarsen(&self, to: Multiple) -> Size {
+        Size {
+            value: math::floor(self.value_in(to)),
+            multiple: to,
+            convention: self.convention,
+        }
+    }
+
+    /// Add `other` to the `Size`, returning `None` if the result overflows.
+    /// The result uses the multiple, and preferred convention, of `self`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size1 = Size::new(500, Multiple::Megabyte).unwrap();
+    /// let size2 = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// println!("size: {}", size1.checked_add(size2).unwrap()); // 1500 MB
+    /// # }
+    /// ```
+    pub fn checked_add(self, other: Size) -> Option<Size> {
+        let value = self.value + other.value_in(self.multiple);
+        if value.is_finite() {
+            Some(Size { value, multiple: self.multiple, convention: self.convention })
+        } else {
+            None
+        }
+    }
+
+    /// Subtract `other` from the `Size`, returning `None` if the result
+    /// would be negative. The result uses the multiple, and preferred
+    /// convention, of `self`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size1 = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// let size2 = Size::new(500, Multiple::Megabyte).unwrap();
+    /// println!("size: {}", size1.checked_sub(size2).unwrap()); // 0.5 GB
+    /// assert!(size2.checked_sub(size1).is_none());
+    /// # }
+    /// ```
+    pub fn checked_sub(self, other: Size) -> Option<Size> {
+        let value = self.value - other.value_in(self.multiple);
+        if value >= 0.0 && value.is_finite() {
+            Some(Size { value, multiple: self.multiple, convention: self.convention })
+        } else {
+            None
+        }
+    }
+
+    /// The absolute difference between two sizes, e.g. to compare directory
+    /// snapshots. Unlike subtracting this can't become negative, so the order
+    /// of the sizes doesn't matter. The result is normalized, see
+    /// [`normalize`], using a binary multiple if both sizes use one.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size1 = Size::new(1, Multiple::Megabyte).unwrap();
+    /// let size2 = Size::new(1, Multiple::Kilobyte).unwrap();
+    /// println!("difference: {}", size1.abs_diff(&size2)); // 999 kB
+    /// assert_eq!(size1.abs_diff(&size2), size2.abs_diff(&size1));
+    /// # }
+    /// ```
+    ///
+    /// [`normalize`]: #method.normalize
+    pub fn abs_diff(&self, other: &Size) -> Size {
+        let binary = self.multiple.is_binary() && other.multiple.is_binary();
+        let bytes = math::abs(self.into_bytes() - other.into_bytes());
+        if bytes.is_finite() {
+            Size::normalized_bytes(bytes, binary)
+        } else {
+            // Overflowed when converting into bytes, so use the larger
+            // multiple instead, which can't overflow.
+            let multiple = cmp::max_by_key(self.multiple, other.multiple, |m| m.multiple_of_bytes());
+            let value = math::abs(self.value_in(multiple) - other.value_in(multiple));
+            Size { value, multiple, convention: None }
+        }
+    }
+
+    /// Scale the `Size` by a (fractional) `factor`, e.g. 1.1 to project 10%
+    /// growth. The number of bytes is rounded to the nearest byte and the
+    /// result is normalized, see [`normalize`]. See [`checked_scale_ratio`]
+    /// to keep the multiple instead.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `factor` is negative or NaN, or if the result
+    /// overflows.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1, Multiple::Kilobyte).unwrap();
+    /// println!("size: {}", size.scale(1.5)); // 1500 B
+    /// # }
+    /// ```
+    ///
+    /// [`normalize`]: #method.normalize
+    /// [`checked_scale_ratio`]: #method.checked_scale_ratio
+    pub fn scale(&self, factor: f64) -> Size {
+        assert!(factor >= 0.0, "invalid factor to scale size: {}", factor);
+        let bytes = math::round(self.into_bytes() * factor);
+        assert!(bytes.is_finite(), "overflow when scaling size");
+        Size::normalized_bytes(bytes, self.multiple.is_binary())
+    }
+
+    /// The ratio of the `Size` to `other`, e.g. 0.25 for 250 MB of 1 GB, to
+    /// show the used part of a quota. If `other` is zero the ratio is
+    /// infinite, or NaN if the `Size` is zero as well, like dividing floats.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let used = Size::new(250, Multiple::Megabyte).unwrap();
+    /// let total = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// assert_eq!(used.ratio(&total), 0.25);
+    /// println!("used {:.0}% of quota", used.ratio(&total) * 100.0); // 25%
+    /// # }
+    /// ```
+    pub fn ratio(&self, other: &Size) -> f64 {
+        // Using the larger multiple avoids overflowing when converting huge
+        // sizes into bytes.
+        let multiple = cmp::max_by_key(self.multiple, other.multiple, |m| m.multiple_of_bytes());
+        self.value_in(multiple) / other.value_in(multiple)
+    }
+
+    /// The [`Throughput`] of transferring the `Size` in `elapsed` time, e.g.
+    /// at the end of a download. If `elapsed` is zero the rate is infinite,
+    /// unless the `Size` is zero as well, see
+    /// [`Throughput::bytes_per_second`].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use std::time::Duration;
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(100, Multiple::Megabyte).unwrap();
+    /// let throughput = size.rate_over(Duration::from_secs(4));
+    /// assert_eq!(throughput.bytes_per_second(), 25_000_000.0);
+    /// # }
+    /// ```
+    ///
+    /// [`Throughput`]: struct.Throughput.html
+    /// [`Throughput::bytes_per_second`]: struct.Throughput.html#method.bytes_per_second
+    pub fn rate_over(&self, elapsed: Duration) -> Throughput {
+        Throughput::new(*self, elapsed)
+    }
+
+    /// Multiply the `Size` by `factor`, keeping the multiple. Returns `None`
+    /// if the result overflows.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(2, Multiple::Gigabyte).unwrap();
+    /// println!("size: {}", size.checked_mul(3).unwrap()); // 6 GB
+    /// # }
+    /// ```
+    pub fn checked_mul(self, factor: u32) -> Option<Size> {
+        let value = self.value * f64::from(factor);
+        if value.is_finite() {
+            Some(Size { value, multiple: self.multiple, convention: self.convention })
+        } else {
+            None
+        }
+    }
+
+    /// Divide the `Size` by `divisor`, keeping the multiple. Returns `None` if
+    /// `divisor` is zero. Since `Size` supports fractional values the result
+    /// is not truncated, e.g. "3 GB" divided by two is "1.5 GB".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(3, Multiple::Gigabyte).unwrap();
+    /// println!("size: {}", size.checked_div(2).unwrap()); // 1.5 GB
+    /// assert!(size.checked_div(0).is_none());
+    /// # }
+    /// ```
+    pub fn checked_div(self, divisor: u32) -> Option<Size> {
+        if divisor == 0 {
+            None
+        } else {
+            let value = self.value / f64::from(divisor);
+            Some(Size { value, multiple: self.multiple, convention: self.convention })
+        }
+    }
+
+    /// Format the `Size` with three or four significant digits, choosing the
+    /// multiple of `convention` like [`humanize`]. Values below 10 are shown
+    /// with two decimals, values below 100 with one decimal and larger values
+    /// without decimals, e.g. "1.50 GiB", "15.0 GiB" and "150 GiB". Values of
+    /// 1000 or more only occur for binary multiples (up to 1023) and the
+    /// largest multiple. Trailing zeros are kept, so all values have the same
+    /// precision. Sizes in bytes are the exception, they are always shown as
+    /// whole bytes, e.g. "3 B" rather than "3.00 B". The multiple is picked
+    /// after rounding, so e.g. 999.6 kB is shown as "1.00 MB", not "1000 kB".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Convention};
+    /// let size = Size::new(1536, Multiple::Mebibyte).unwrap();
+    /// assert_eq!(size.to_nice_string(Convention::Binary), "1.50 GiB");
+    /// let size = Size::new(3, Multiple::Byte).unwrap();
+    /// assert_eq!(size.to_nice_string(Convention::Binary), "3 B");
+    /// # }
+    /// ```
+    ///
+    /// [`humanize`]: #method.humanize
+    #[cfg(feature = "alloc")]
+    pub fn to_nice_string(&self, convention: Convention) -> String {
+        let multiples = convention.multiples();
+        let mut size = self.humanize_using(convention, u64::MAX);
+        loop {
+            // Use the rounded value to pick the precision, so that e.g. 9.999
+            // is shown as "10.0" rather than "10.00".
+            let mut precision = if size.multiple == Multiple::Byte { 0 } else { 2 };
+            let mut rounded: f64;
+            loop {
+                rounded = format!("{:.*}", precision, math::abs(size.value))
+                    .parse().unwrap_or(0.0);
+                if precision == 0 || rounded < math::powi(10.0, 3 - precision as i32) {
+                    break;
+                }
+                precision -= 1;
+            }
+
+            // Rounding can reach the next multiple, e.g. 1023.7 KiB is
+            // rounded to "1024", use the next multiple instead.
+            let next = multiples.iter()
+                .position(|multiple| *multiple == size.multiple)
+                .and_then(|index| multiples.get(index + 1));
+            match next {
+                Some(&next) if rounded >= (next.multiple_of_bytes() / size.multiple.multiple_of_bytes()) as f64 => {
+                    size = Size { value: size.value_in(next), multiple: next, convention: size.convention };
+                },
+                _ => return format!("{:.*} {}", precision, size.value, size.multiple),
+            }
+        }
+    }
+
+    /// Returns the smallest of `containers` that can hold the `Size`, i.e.
+    /// that is at least as large, or `None` if none of them are large enough.
+    /// The containers don't have to be sorted, if multiple containers are
+    /// equally large the first is returned.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let cd = Size::new(700, Multiple::Megabyte).unwrap();
+    /// let dvd = Size::new(4.7, Multiple::Gigabyte).unwrap();
+    ///
+    /// let size = Size::new(1, Multiple::Gigabyte).unwrap();
+    /// assert_eq!(size.smallest_container(&[dvd, cd]), Some(dvd));
+    /// # }
+    /// ```
+    pub fn smallest_container(&self, containers: &[Size]) -> Option<Size> {
+        containers.iter()
+            .filter(|container| *container >= self)
+            .min_by(|a, b| a.compare(b))
+            .cloned()
+    }
+
+    /// Write the `Size` into `writer`, without allocating. This writes the same
+    /// output as the [`Display`] implementation (without any formatting
+    /// options), e.g. to write into a fixed size buffer.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(10, Multiple::Megabyte).unwrap();
+    /// let mut output = String::new();
+    /// size.write_to(&mut output).unwrap();
+    /// assert_eq!(output, "10 MB");
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/nightly/core/fmt/trait.Display.html
+    pub fn write_to<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "{} {}", self.value, self.multiple)
+    }
+
+    /// Replace the multiple of the `Size` with the result of `f`, keeping the
+    /// value. Note that this is a reinterpretation, not a conversion: it
+    /// changes the size in bytes, e.g. "10 kB" can become "10 MB".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(10, Multiple::Kilobyte).unwrap();
+    /// let size = size.map_multiple(|multiple| multiple.next_larger().unwrap_or(multiple));
+    /// println!("size: {}", size); // 10 MB
+    /// # }
+    /// ```
+    pub fn map_multiple<F>(self, f: F) -> Size
+        where F: FnOnce(Multiple) -> Multiple,
+    {
+        Size { value: self.value, multiple: f(self.multiple), convention: self.convention }
+    }
+
+    /// Format the `Size` like the [`Display`] implementation, but using the
+    /// symbols from `aliases` for the multiples it contains. Multiples not in
+    /// `aliases` use their default symbol.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let aliases = [(Multiple::Kilobyte, "Kbyte"), (Multiple::Megabyte, "Mbyte")];
+    /// let size = Size::new(10, Multiple::Kilobyte).unwrap();
+    /// assert_eq!(size.format_with_aliases(&aliases), "10 Kbyte");
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/nightly/core/fmt/trait.Display.html
+    #[cfg(feature = "alloc")]
+    pub fn format_with_aliases(&self, aliases: &[(Multiple, &str)]) -> String {
+        let symbol = aliases.iter()
+            .find(|&&(multiple, _)| multiple == self.multiple)
+            .map_or(self.multiple.symbol(), |&(_, symbol)| symbol);
+        format!("{} {}", self.value, symbol)
+    }
+
+    /// The size in (whole) bytes as an `u64`, saturating at the bounds of
+    /// `u64`. Sizes larger than `u64::MAX` bytes return `u64::MAX`, negative
+    /// sizes return zero and fractional bytes are truncated. This is useful
+    /// when exporting sizes as metrics, e.g. as Prometheus gauges, that must
+    /// always have a value.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1, Multiple::Kibibyte).unwrap();
+    /// assert_eq!(size.to_bytes_saturating_u64(), 1024);
+    ///
+    /// let size = Size::new(1e10, Multiple::Pebibyte).unwrap();
+    /// assert_eq!(size.to_bytes_saturating_u64(), u64::MAX);
+    /// # }
+    /// ```
+    pub fn to_bytes_saturating_u64(&self) -> u64 {
+        // Casting a float to an integer saturates.
+        self.into_bytes() as u64
+    }
+
+    /// Old name of [`to_bytes_saturating_u64`].
+    ///
+    /// [`to_bytes_saturating_u64`]: #method.to_bytes_saturating_u64
+    #[deprecated(note = "renamed to `to_bytes_saturating_u64`")]
+    pub fn bytes_saturating_u64(&self) -> u64 {
+        self.to_bytes_saturating_u64()
+    }
+
+    /// The size in (whole) bytes as an `u64`, or `None` if the size is
+    /// negative or larger than `u64::MAX` bytes. Fractional bytes are
+    /// truncated. This is the same as [`to_integer`], but ignoring the kind
+    /// of error.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1, Multiple::Kilobyte).unwrap();
+    /// assert_eq!(size.to_bytes_checked_u64(), Some(1000));
+    ///
+    /// let size = Size::new(1e10, Multiple::Pebibyte).unwrap();
+    /// assert_eq!(size.to_bytes_checked_u64(), None);
+    /// # }
+    /// ```
+    ///
+    /// [`to_integer`]: #method.to_integer
+    pub fn to_bytes_checked_u64(&self) -> Option<u64> {
+        self.to_integer().ok()
+    }
+
+    /// Same as [`to_bytes_saturating_u64`], but for `u32`. Sizes larger than
+    /// `u32::MAX` bytes (4 GiB) return `u32::MAX`.
+    ///
+    /// [`to_bytes_saturating_u64`]: #method.to_bytes_saturating_u64
+    pub fn to_bytes_saturating_u32(&self) -> u32 {
+        // Casting a float to an integer saturates.
+        self.into_bytes() as u32
+    }
+
+    /// Same as [`to_bytes_checked_u64`], but for `u32`.
+    ///
+    /// [`to_bytes_checked_u64`]: #method.to_bytes_checked_u64
+    pub fn to_bytes_checked_u32(&self) -> Option<u32> {
+        self.to_integer().ok()
+    }
+
+    /// The size in bytes as an `u128`, rounding fractional bytes using
+    /// `rounding`. Like [`as_bytes`] this never fails, negative sizes return
+    /// zero and sizes larger than `u128::MAX` bytes return `u128::MAX`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, Rounding};
+    /// let size = Size::new(1.5, Multiple::Byte).unwrap();
+    /// assert_eq!(size.to_bytes_rounded(Rounding::Floor), 1);
+    /// assert_eq!(size.to_bytes_rounded(Rounding::Ceil), 2);
+    /// # }
+    /// ```
+    ///
+    /// [`as_bytes`]: #method.as_bytes
+    pub fn to_bytes_rounded(&self, rounding: Rounding) -> u128 {
+        let bytes = self.into_bytes();
+        let bytes = match rounding {
+            Rounding::Floor => math::floor(bytes),
+            Rounding::Ceil => math::ceil(bytes),
+            Rounding::Nearest => math::round(bytes),
+            Rounding::Truncate => math::trunc(bytes),
+        };
+        // Casting a float to an integer saturates.
+        bytes as u128
+    }
+
+    /// The size in (whole) bytes as an `u128`. Unlike converting using
+    /// [`to_integer`] this never fails, fractional bytes are truncated,
+    /// negative sizes return zero and sizes larger than `u128::MAX` bytes,
+    /// which can only be created using very large values, return
+    /// `u128::MAX`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1, Multiple::Pebibyte).unwrap();
+    /// assert_eq!(size.as_bytes(), 1_125_899_906_842_624);
+    /// # }
+    /// ```
+    ///
+    /// [`to_integer`]: #method.to_integer
+    pub fn as_bytes(&self) -> u128 {
+        // Casting a float to an integer saturates.
+        self.into_bytes() as u128
+    }
+
+    /// Create a `Size` from `bytes` using the largest multiple of
+    /// `convention` that divides `bytes` evenly.
+    fn from_bytes_using(bytes: u128, convention: Convention) -> Size {
+        let multiple = if bytes == 0 {
+            Multiple::Byte
+        } else {
+            convention.multiples().iter().rev()
+                .find(|multiple| bytes % u128::from(multiple.multiple_of_bytes()) == 0)
+                .cloned()
+                .unwrap_or(Multiple::Byte)
+        };
+        let value = bytes / u128::from(multiple.multiple_of_bytes());
+        Size { value: value as f64, multiple, convention: None }
+    }
+
+    /// Describe the `Size` in English words, e.g. "1.5 billion bytes", for
+    /// example for screen readers. The size in bytes is expressed using the
+    /// largest of the (short scale) magnitudes thousand, million, billion,
+    /// trillion, quadrillion and quintillion (10^18) that is not larger than
+    /// it, larger sizes use quintillion. Sizes smaller than a thousand bytes
+    /// don't use a magnitude. At most two decimals are shown, if the rounded
+    /// value reaches a thousand the next magnitude is used, e.g. 999999 bytes
+    /// is "1 million bytes", not "1000 thousand bytes".
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(1.5, Multiple::Gigabyte).unwrap();
+    /// assert_eq!(size.to_words(), "1.5 billion bytes");
+    /// let size = Size::new(1, Multiple::Byte).unwrap();
+    /// assert_eq!(size.to_words(), "1 byte");
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_words(&self) -> String {
+        const MAGNITUDES: [(&str, f64); 7] = [
+            ("", 1.0),
+            ("thousand", 1e3),
+            ("million", 1e6),
+            ("billion", 1e9),
+            ("trillion", 1e12),
+            ("quadrillion", 1e15),
+            ("quintillion", 1e18),
+        ];
+
+        let bytes = self.into_bytes();
+        let options = FormatOptions::new().max_precision(2);
+        let mut index = MAGNITUDES.iter()
+            .rposition(|&(_, magnitude)| math::abs(bytes) >= magnitude)
+            .unwrap_or(0);
+        let mut value = options.format_value(bytes / MAGNITUDES[index].1);
+        // Rounding can result in a thousand of the magnitude, e.g. 999.999
+        // thousand is written as "1000", use the next magnitude instead.
+        let rounded_up = value.parse::<f64>().map(math::abs).unwrap_or(0.0) >= 1000.0;
+        if rounded_up && index + 1 < MAGNITUDES.len() {
+            index += 1;
+            value = options.format_value(bytes / MAGNITUDES[index].1);
+        }
+        match MAGNITUDES[index].0 {
+            "" if value == "1" => "1 byte".to_owned(),
+            "" => format!("{} bytes", value),
+            name => format!("{} {} bytes", value, name),
+        }
+    }
+
+    /// Format the `Size` using the full name of its multiple, e.g.
+    /// "5 megabytes" rather than "5 MB". The name is singular if the value is
+    /// exactly one, e.g. "1 kilobyte", and plural otherwise, e.g.
+    /// "1.5 kilobytes" or "0 bytes". See [`Multiple::long_name`].
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple};
+    /// let size = Size::new(5, Multiple::Megabyte).unwrap();
+    /// assert_eq!(size.to_string_long(), "5 megabytes");
+    /// let size = Size::new(1, Multiple::Byte).unwrap();
+    /// assert_eq!(size.to_string_long(), "1 byte");
+    /// # }
+    /// ```
+    ///
+    /// [`Multiple::long_name`]: enum.Multiple.html#method.long_name
+    #[cfg(feature = "alloc")]
+    pub fn to_string_long(&self) -> String {
+        let plural = if self.value == 1.0 { "" } else { "s" };
+        format!("{} {}{}", self.value, self.multiple.long_name(), plural)
+    }
+
+    /// Create a normalized `Size`, see [`normalize`], from `bytes`. If
+    /// `binary` is true a binary multiple is used, if possible.
+    ///
+    /// [`normalize`]: #method.normalize
+    fn normalized_bytes(bytes: f64, binary: bool) -> Size {
+        let (value, multiple) = if binary && bytes != 0.0 {
+            (bytes / 1024.0, Multiple::Kibibyte)
+        } else {
+            (bytes, Multiple::Byte)
+        };
+        Size { value, multiple, convention: None }.normalize()
+    }
+
+    /// Compare the size in bytes exactly to `bytes`.
+    fn compare_bytes(&self, bytes: u64) -> Ordering {
+        let size_bytes = self.into_bytes();
+        if size_bytes < 0.0 {
+            Ordering::Less
+        } else if size_bytes >= math::powi(2.0, 64) {
+            Ordering::Greater
+        } else {
+            // The size fits in an `u64`, so the cast only truncates the
+            // fractional bytes.
+            match (size_bytes as u64).cmp(&bytes) {
+                Ordering::Equal if math::fract(size_bytes) != 0.0 => Ordering::Greater,
+                ordering => ordering,
+            }
+        }
+    }
+
+    /// Compare two sizes by their size in bytes. If either size overflows
+    /// when converted into bytes the value of the size with the smaller
+    /// multiple is converted into the larger multiple instead, which can't
+    /// overflow.
+    fn compare(&self, other: &Size) -> Ordering {
+        let (left, right) = (self.into_bytes(), other.into_bytes());
+        let (left, right) = if left.is_finite() && right.is_finite() {
+            (left, right)
+        } else if self.multiple.multiple_of_bytes() >= other.multiple.multiple_of_bytes() {
+            (self.value, other.value_in(self.multiple))
+        } else {
+            (self.value_in(other.multiple), other.value)
+        };
+        // Values are never NaN (see `Size::new`), and zero is equal to
+        // negative zero.
+        left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+    }
+
+    /// The convention of the `Size`, either set by the user or inferred from
+    /// the multiple.
+    fn convention(&self) -> Convention {
+        self.convention.unwrap_or_else(|| self.multiple.convention())
+    }
+
+    /// The value of the `Size` expressed in `multiple`.
+    fn value_in(&self, multiple: Multiple) -> f64 {
+        let bytes = self.into_bytes();
+        if bytes.is_finite() {
+            bytes / multiple.multiple_of_bytes() as f64
+        } else {
+            // Overflowed when converting into bytes, so scale the value
+            // directly instead.
+            let ratio = self.multiple.multiple_of_bytes() as f64 /
+                multiple.multiple_of_bytes() as f64;
+            self.value * ratio
+        }
+    }
+}
+
+impl FromStr for Size {
+    type Err = ParsingError;
+
+    fn from_str(input: &str) -> Result<Size, Self::Err> {
+        Size::parse_with(input, &ParseOptions::new())
+    }
+}
+
+/// Sizes are equal if their size in bytes is equal, e.g. "1 kB" equals
+/// "1000 B". Since the value of a `Size` is never NaN, see [`Size::new`], this
+/// equality is reflexive.
+///
+/// [`Size::new`]: struct.Size.html#method.new
+impl Eq for Size {}
+
+impl PartialEq for Size {
+    fn eq(&self, other: &Size) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Size {
+    fn partial_cmp(&self, other: &Size) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sizes are compared by their size in bytes, so e.g. "1000 B" and "1 kB" are
+/// equal.
+impl Ord for Size {
+    fn cmp(&self, other: &Size) -> Ordering {
+        self.compare(other)
+    }
+}
+
+/// Compares the size in bytes to a number of bytes, e.g. `size > 4096`. The
+/// comparison is exact: fractional bytes are taken into account and sizes
+/// larger than `u64::MAX` bytes compare greater than any `u64`.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple};
+/// let size = Size::new(1, Multiple::Kilobyte).unwrap();
+/// assert!(size == 1000u64);
+/// assert!(size > 999u64);
+/// assert!(4096u64 > size);
+/// # }
+/// ```
+impl PartialEq<u64> for Size {
+    fn eq(&self, other: &u64) -> bool {
+        self.compare_bytes(*other) == Ordering::Equal
+    }
+}
+
+/// See the `PartialEq<u64>` implementation.
+impl PartialOrd<u64> for Size {
+    fn partial_cmp(&self, other: &u64) -> Option<Ordering> {
+        Some(self.compare_bytes(*other))
+    }
+}
+
+/// See the `PartialEq<u64>` implementation of `Size`.
+impl PartialEq<Size> for u64 {
+    fn eq(&self, other: &Size) -> bool {
+        other == self
+    }
+}
+
+/// See the `PartialEq<u64>` implementation of `Size`.
+impl PartialOrd<Size> for u64 {
+    fn partial_cmp(&self, other: &Size) -> Option<Ordering> {
+        Some(other.compare_bytes(*self).reverse())
+    }
+}
+
+/// The default `Size` is zero bytes.
+impl Default for Size {
+    fn default() -> Size {
+        Size::ZERO
+    }
+}
+
+/// Sizes are hashed by their size in bytes, consistent with the `Eq`
+/// implementation, so e.g. "1000 B" and "1 kB" have the same hash.
+impl Hash for Size {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Adding zero maps negative zero to (positive) zero. Sizes that
+        // overflow when converted into bytes all hash as infinite.
+        (self.into_bytes() + 0.0).to_bits().hash(state)
+    }
+}
+
+/// Adds two sizes, see [`Size::checked_add`].
+///
+/// # Panics
+///
+/// This panics if the result overflows, use [`Size::checked_add`] to handle
+/// overflows.
+///
+/// [`Size::checked_add`]: struct.Size.html#method.checked_add
+impl Add for Size {
+    type Output = Size;
+
+    fn add(self, other: Size) -> Size {
+        self.checked_add(other).expect("overflow when adding sizes")
+    }
+}
+
+/// Subtracts two sizes, see [`Size::checked_sub`]. If the result would be
+/// negative it saturates at zero.
+///
+/// [`Size::checked_sub`]: struct.Size.html#method.checked_sub
+impl Sub for Size {
+    type Output = Size;
+
+    fn sub(self, other: Size) -> Size {
+        self.checked_sub(other).unwrap_or(Size {
+            value: 0.0,
+            multiple: self.multiple,
+            convention: self.convention,
+        })
+    }
+}
+
+/// Adds a size in place, e.g. `total += size`, see the `Add` implementation.
+///
+/// # Panics
+///
+/// This panics if the result overflows, use [`Size::checked_add`] to handle
+/// overflows.
+///
+/// [`Size::checked_add`]: struct.Size.html#method.checked_add
+impl AddAssign for Size {
+    fn add_assign(&mut self, other: Size) {
+        *self = *self + other;
+    }
+}
+
+/// Subtracts a size in place, e.g. `remaining -= size`, see the `Sub`
+/// implementation. If the result would be negative it saturates at zero.
+impl SubAssign for Size {
+    fn sub_assign(&mut self, other: Size) {
+        *self = *self - other;
+    }
+}
+
+/// Multiplies a size, see [`Size::checked_mul`].
+///
+/// # Panics
+///
+/// This panics if the result overflows, use [`Size::checked_mul`] to handle
+/// overflows.
+///
+/// [`Size::checked_mul`]: struct.Size.html#method.checked_mul
+impl Mul<u32> for Size {
+    type Output = Size;
+
+    fn mul(self, factor: u32) -> Size {
+        self.checked_mul(factor).expect("overflow when multiplying size")
+    }
+}
+
+/// Divides a size, see [`Size::checked_div`].
+///
+/// # Panics
+///
+/// This panics if `divisor` is zero, use [`Size::checked_div`] to handle
+/// zero divisors.
+///
+/// [`Size::checked_div`]: struct.Size.html#method.checked_div
+impl Div<u32> for Size {
+    type Output = Size;
+
+    fn div(self, divisor: u32) -> Size {
+        self.checked_div(divisor).expect("attempt to divide size by zero")
+    }
+}
+
+/// Sums sizes by adding their sizes in bytes. The result is normalized, see
+/// [`Size::normalize`], e.g. summing "1 kB" and "500 B" results in "1500 B"
+/// and summing "1 kB" and "1000 B" in "2 kB". If all sizes use a binary
+/// multiple the result does as well, if possible. The sum of no sizes is zero
+/// bytes.
+///
+/// # Panics
+///
+/// This panics if the sum overflows, like adding sizes using `+` does.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple};
+/// let sizes = [
+///     Size::new(1, Multiple::Kilobyte).unwrap(),
+///     Size::new(500, Multiple::Byte).unwrap(),
+/// ];
+/// let total: Size = sizes.iter().sum();
+/// println!("total: {}", total); // 1500 B
+/// # }
+/// ```
+///
+/// [`Size::normalize`]: struct.Size.html#method.normalize
+impl Sum for Size {
+    fn sum<I>(iter: I) -> Size
+        where I: Iterator<Item = Size>,
+    {
+        let (bytes, binary) = iter.fold((0.0, true), |(bytes, binary), size| {
+            (bytes + size.into_bytes(), binary && size.multiple.is_binary())
+        });
+        assert!(bytes.is_finite(), "overflow when adding sizes");
+        Size::normalized_bytes(bytes, binary)
+    }
+}
+
+/// Sums sizes by reference, see the implementation for owned sizes.
+impl<'a> Sum<&'a Size> for Size {
+    fn sum<I>(iter: I) -> Size
+        where I: Iterator<Item = &'a Size>,
+    {
+        iter.cloned().sum()
+    }
+}
+
+/// Collects sizes into their total, see the implementation of `Sum`.
+///
+/// # Panics
+///
+/// This panics if the total overflows, like summing the sizes would.
+impl FromIterator<Size> for Size {
+    fn from_iter<I>(iter: I) -> Size
+        where I: IntoIterator<Item = Size>,
+    {
+        iter.into_iter().sum()
+    }
+}
+
+/// Converts the size into a number of bytes as `usize`, e.g. to allocate a
+/// buffer, see [`Size::to_integer`]. Whether or not the size fits depends on
+/// the pointer width of the target, e.g. 4 GiB doesn't fit on 32 bit
+/// targets, but sizes that fit in an `u32` always fit.
+///
+/// [`Size::to_integer`]: struct.Size.html#method.to_integer
+impl TryFrom<Size> for usize {
+    type Error = ConversionError;
+
+    fn try_from(size: Size) -> Result<usize, ConversionError> {
+        size.to_integer()
+    }
+}
+
+/// Converts the size into (fractional) bytes, see [`Size::into_bytes`]. Note
+/// that sizes above 2^53 bytes (8 PiB) lose integer precision, and sizes
+/// overflowing an `f64` become infinite, but the conversion never fails.
+///
+/// [`Size::into_bytes`]: struct.Size.html#method.into_bytes
+impl From<Size> for f64 {
+    fn from(size: Size) -> f64 {
+        size.into_bytes()
+    }
+}
+
+/// By default the `Size` is displayed using its own value and multiple, e.g.
+/// "1500 kB", the value is never scaled. Using the alternate flag, i.e.
+/// `{:#}`, the `Size` is first humanized (see [`Size::humanize`]) and
+/// formatted using the default [`FormatOptions`]. The alternate flag prefers
+/// binary multiples, e.g. 1048576 bytes is displayed as "1 MiB", unless a
+/// convention is set using [`Size::with_convention`], then that convention is
+/// used.
+///
+/// The precision, e.g. `{:.2}`, sets the number of decimals of the value.
+/// The width, fill and alignment, e.g. `{:>12}`, apply to the entire size,
+/// by default the size is left aligned.
+///
+/// The default format round-trips: parsing it using [`FromStr`] results in
+/// a `Size` with the same value and multiple, for all multiples and all
+/// sizes that aren't negative. Note that this doesn't hold when using any of
+/// the flags, e.g. a precision may round the value.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple, Convention};
+/// let size = Size::new(1500, Multiple::Kilobyte).unwrap();
+/// assert_eq!(format!("{}", size), "1500 kB");
+/// assert_eq!(format!("{}", size).parse::<Size>().unwrap().multiple(), Multiple::Kilobyte);
+/// assert_eq!(format!("{:#}", size), "1.430511 MiB");
+/// assert_eq!(format!("[{:>10}]", size), "[   1500 kB]");
+///
+/// let size = size.with_convention(Convention::Decimal);
+/// assert_eq!(format!("{:#}", size), "1.5 MB");
+/// assert_eq!(format!("{:#.2}", size), "1.50 MB");
+///
+/// let size = Size::new(1_048_576, Multiple::Byte).unwrap();
+/// assert_eq!(format!("{}", size), "1048576 B");
+/// assert_eq!(format!("{:#}", size), "1 MiB");
+/// # }
+/// ```
+///
+/// [`Size::humanize`]: struct.Size.html#method.humanize
+/// [`Size::with_convention`]: struct.Size.html#method.with_convention
+/// [`FormatOptions`]: struct.FormatOptions.html
+/// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = if f.alternate() {
+            let convention = self.convention.unwrap_or(Convention::Binary);
+            self.with_convention(convention).humanize()
+        } else {
+            *self
+        };
+        let (alternate, precision) = (f.alternate(), f.precision());
+        let write = |w: &mut dyn Write| match precision {
+            Some(precision) => write!(w, "{:.*} {}", precision, size.value, size.multiple),
+            None if alternate => size.write_with(w, &FormatOptions::new()),
+            None => write!(w, "{} {}", size.value, size.multiple),
+        };
+
+        // Can't use `Formatter::pad` as that would also apply the precision.
+        // Instead the output is written twice, first to determine its length.
+        let mut length = writers::CharCount(0);
+        write(&mut length)?;
+        let padding = f.width()
+            .map_or(0, |width| width.saturating_sub(length.0));
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Left) | None => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        write(f)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sort `sizes` by their size in bytes, from small to large. The sort is
+/// stable, so sizes that are equal keep their original order.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple, sort_sizes};
+/// let mut sizes = vec![
+///     Size::new(1, Multiple::Kibibyte).unwrap(),
+///     Size::new(1, Multiple::Kilobyte).unwrap(),
+///     Size::new(10, Multiple::Byte).unwrap(),
+/// ];
+/// sort_sizes(&mut sizes);
+/// println!("sizes: {:?}", sizes); // 10 B, 1 kB, 1 KiB
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sort_sizes(sizes: &mut [Size]) {
+    sizes.sort_by(|a, b| a.compare(b));
+}
+
+/// Sort `sizes` by their size in bytes, from large to small. The sort is
+/// stable, so sizes that are equal keep their original order.
+#[cfg(feature = "alloc")]
+pub fn sort_sizes_desc(sizes: &mut [Size]) {
+    sizes.sort_by(|a, b| b.compare(a));
+}
+
+/// Format the range from `lo` to `hi` using a single multiple for both ends,
+/// e.g. "0.5–2 GB" rather than "500 MB – 2 GB".
+///
+/// The multiple is chosen based on the larger end of the range: it's the
+/// multiple `hi` (or `lo` if that is larger) would get when humanized using
+/// `convention`, see [`Size::humanize`]. The values are formatted like
+/// [`Size::format`] with the default [`FormatOptions`].
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple, Convention, format_range};
+/// let lo = Size::new(500, Multiple::Megabyte).unwrap();
+/// let hi = Size::new(2, Multiple::Gigabyte).unwrap();
+/// assert_eq!(format_range(lo, hi, Convention::Decimal), "0.5–2 GB");
+/// # }
+/// ```
+///
+/// [`Size::humanize`]: struct.Size.html#method.humanize
+/// [`Size::format`]: struct.Size.html#method.format
+/// [`FormatOptions`]: struct.FormatOptions.html
+#[cfg(feature = "alloc")]
+pub fn format_range(lo: Size, hi: Size, convention: Convention) -> String {
+    let larger = if math::abs(lo.into_bytes()) > math::abs(hi.into_bytes()) { lo } else { hi };
+    let multiple = larger.humanize_using(convention, u64::MAX).multiple;
+    let options = FormatOptions::new();
+    format!("{}–{} {}", options.format_value(lo.value_in(multiple)),
+        options.format_value(hi.value_in(multiple)), multiple)
+}
+
+/// Options used to format a [`Size`] using [`Size::format`].
+///
+/// By default at most six decimals are shown, so that sizes such as
+/// "0.95367431640625 MiB" are shown as "0.953674 MiB". Trailing zeros are
+/// never shown.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple, Convention, FormatOptions};
+/// let size = Size::new(1, Multiple::Gigabyte).unwrap()
+///     .with_convention(Convention::Binary)
+///     .humanize();
+/// println!("size: {}", size); // 953.67431640625 MiB
+/// println!("size: {}", size.format(&FormatOptions::new())); // 953.674316 MiB
+///
+/// let options = FormatOptions::new().max_precision(2);
+/// println!("size: {}", size.format(&options)); // 953.67 MiB
+/// # }
+/// ```
+///
+/// [`Size`]: struct.Size.html
+/// [`Size::format`]: struct.Size.html#method.format
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FormatOptions {
+    max_precision: usize,
+    uppercase_kilo: bool,
+    group_separator: Option<char>,
+}
+
+/// The default maximum number of decimals shown by [`FormatOptions`].
+const DEFAULT_MAX_PRECISION: usize = 6;
+
+impl FormatOptions {
+    /// Create the default `FormatOptions`.
+    pub fn new() -> FormatOptions {
+        FormatOptions {
+            max_precision: DEFAULT_MAX_PRECISION,
+            uppercase_kilo: false,
+            group_separator: None,
+        }
+    }
+
+    /// Set the maximum number of decimals shown, defaults to six.
+    pub fn max_precision(mut self, max_precision: usize) -> FormatOptions {
+        self.max_precision = max_precision;
+        self
+    }
+
+    /// Use an uppercase "K" for the symbol of `Kilobyte`, i.e. "KB" rather
+    /// than "kB", defaults to false. Note that by default "KB" is parsed as
+    /// `Kibibyte`, use [`ParseOptions::uppercase_kilo`] to parse it as
+    /// `Kilobyte`.
+    ///
+    /// [`ParseOptions::uppercase_kilo`]: struct.ParseOptions.html#method.uppercase_kilo
+    pub fn uppercase_kilo(mut self, uppercase_kilo: bool) -> FormatOptions {
+        self.uppercase_kilo = uppercase_kilo;
+        self
+    }
+
+    /// Separate every group of three digits in the integer part of the value
+    /// using `separator`, e.g. "1,234,567 B" using a comma or "1 234 567 B"
+    /// using a space. By default the digits aren't grouped.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, FormatOptions};
+    /// let size = Size::new(1234567.5, Multiple::Byte).unwrap();
+    /// let options = FormatOptions::new().group_separator(',');
+    /// assert_eq!(size.format(&options), "1,234,567.5 B");
+    /// # }
+    /// ```
+    pub fn group_separator(mut self, separator: char) -> FormatOptions {
+        self.group_separator = Some(separator);
+        self
+    }
+
+    /// The symbol used for `multiple`.
+    fn symbol(&self, multiple: Multiple) -> &'static str {
+        match multiple {
+            Multiple::Kilobyte if self.uppercase_kilo => "KB",
+            multiple => multiple.symbol(),
+        }
+    }
+
+    /// Format `value` using at most `max_precision` decimals, removing any
+    /// trailing zeros.
+    #[cfg(feature = "alloc")]
+    fn format_value(&self, value: f64) -> String {
+        let mut output = String::new();
+        let _ = self.write_value(&mut output, value);
+        output
+    }
+
+    /// Write `value` into `writer`, see [`format_value`].
+    ///
+    /// [`format_value`]: #method.format_value
+    fn write_value<W: Write + ?Sized>(&self, writer: &mut W, value: f64) -> fmt::Result {
+        // Negative values that are rounded to zero are written as "0", rather
+        // than "-0".
+        let mut is_zero = writers::IsZero(true);
+        write!(is_zero, "{:.*}", self.max_precision, value)?;
+        let value = if is_zero.0 { 0.0 } else { value };
+        match self.group_separator {
+            Some(separator) => {
+                let mut digits = writers::IntegerDigits::new();
+                write!(digits, "{:.*}", self.max_precision, value)?;
+                let mut writer = writers::GroupDigits::new(writer, separator, digits.count());
+                write!(writers::TrimZeros::new(&mut writer), "{:.*}", self.max_precision, value)
+            },
+            None => write!(writers::TrimZeros::new(writer), "{:.*}", self.max_precision, value),
+        }
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions::new()
+    }
+}
+
+/// Options used to parse sizes written in a localized format using
+/// [`Size::parse_localized`], e.g. "1,234.5 MB" (US) or "1 234,5 MB"
+/// (European).
+///
+/// By default the decimal separator is a period ('.') and no group separator
+/// is used, matching the [`FromStr`] implementation of [`Size`]. The group
+/// separator must differ from the decimal separator, commonly used group
+/// separators are a comma (','), period ('.'), space (' ') or thin space
+/// ('\u{2009}').
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, LocaleOptions};
+/// let us = LocaleOptions::new().group_separator(',');
+/// let european = LocaleOptions::new().decimal_separator(',').group_separator(' ');
+///
+/// let size1 = Size::parse_localized("1,234.5 MB", &us).unwrap();
+/// let size2 = Size::parse_localized("1 234,5 MB", &european).unwrap();
+/// assert_eq!(size1, size2);
+/// # }
+/// ```
+///
+/// [`Size::parse_localized`]: struct.Size.html#method.parse_localized
+/// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+/// [`Size`]: struct.Size.html
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LocaleOptions {
+    decimal_separator: char,
+    group_separator: Option<char>,
+}
+
+#[cfg(feature = "alloc")]
+impl LocaleOptions {
+    /// Create the default `LocaleOptions`.
+    pub fn new() -> LocaleOptions {
+        LocaleOptions {
+            decimal_separator: '.',
+            group_separator: None,
+        }
+    }
+
+    /// Set the decimal separator, defaults to a period ('.').
+    pub fn decimal_separator(mut self, separator: char) -> LocaleOptions {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Set the separator used to group thousands, by default grouping is not
+    /// allowed. If set the value must be correctly grouped, i.e. in groups of
+    /// three digits.
+    ///
+    /// Each character has a single meaning per call: if the group separator
+    /// is the same as the decimal separator it's only used as decimal
+    /// separator, and separators not used by the locale, e.g. a period when
+    /// using a comma as decimal separator without grouping, are rejected
+    /// rather than guessed.
+    pub fn group_separator(mut self, separator: char) -> LocaleOptions {
+        self.group_separator = Some(separator);
+        self
+    }
+
+    /// Converts the localized number at the start of `input` into a number
+    /// that can be parsed by `f64`'s `FromStr` implementation. Returns the
+    /// number and the remainder of `input`.
+    fn normalize_number<'a>(&self, input: &'a str) -> Result<(String, &'a str), ParsingError> {
+        let mut number = String::with_capacity(input.len());
+        // Number of digits in the current group and whether or not any group
+        // separator was found.
+        let mut digits = 0;
+        let mut grouped = false;
+        let mut decimal = false;
+        let mut end = input.len();
+
+        let mut chars = input.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                digits += 1;
+            } else if c == self.decimal_separator {
+                if decimal || (grouped && digits != 3) {
+                    return Err(ParsingError::InvalidValue);
+                }
+                number.push('.');
+                decimal = true;
+            } else if Some(c) == self.group_separator && !decimal &&
+                chars.peek().map_or(false, |&(_, c)| c.is_ascii_digit())
+            {
+                if digits == 0 || digits > 3 || (grouped && digits != 3) {
+                    return Err(ParsingError::InvalidValue);
+                }
+                grouped = true;
+                digits = 0;
+            } else if c.is_numeric() || c == '.' || c == ',' {
+                // Separator not used by the locale.
+                return Err(ParsingError::InvalidValue);
+            } else {
+                end = index;
+                break;
+            }
+        }
+
+        if grouped && !decimal && digits != 3 {
+            return Err(ParsingError::InvalidValue);
+        }
+        Ok((number, &input[end..]))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for LocaleOptions {
+    fn default() -> LocaleOptions {
+        LocaleOptions::new()
+    }
+}
+
+/// Options used to parse sizes using [`Size::parse_with`].
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::{Size, Multiple, ParseOptions};
+/// let size = Size::parse_with("1 KB", &ParseOptions::new()).unwrap();
+/// assert_eq!(size, Size::new(1, Multiple::Kibibyte).unwrap());
+///
+/// let options = ParseOptions::new().uppercase_kilo(true);
+/// let size = Size::parse_with("1 KB", &options).unwrap();
+/// assert_eq!(size, Size::new(1, Multiple::Kilobyte).unwrap());
+/// # }
+/// ```
+///
+/// [`Size::parse_with`]: struct.Size.html#method.parse_with
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParseOptions {
+    uppercase_kilo: bool,
+    case_insensitive: bool,
+    single_letter: bool,
+}
+
+impl ParseOptions {
+    /// Create the default `ParseOptions`, which parse sizes the same way as
+    /// the [`FromStr`] implementation of [`Size`].
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+    /// [`Size`]: struct.Size.html
+    pub fn new() -> ParseOptions {
+        ParseOptions {
+            uppercase_kilo: false,
+            case_insensitive: false,
+            single_letter: false,
+        }
+    }
+
+    /// Parse "KB" as `Kilobyte`, rather than `Kibibyte`, defaults to false.
+    /// This matches sizes formatted using [`FormatOptions::uppercase_kilo`].
+    ///
+    /// [`FormatOptions::uppercase_kilo`]: struct.FormatOptions.html#method.uppercase_kilo
+    pub fn uppercase_kilo(mut self, uppercase_kilo: bool) -> ParseOptions {
+        self.uppercase_kilo = uppercase_kilo;
+        self
+    }
+
+    /// Parse multiples ignoring case, e.g. "mb", "Mb" and "MB" are all parsed
+    /// as `Megabyte` and "gib" as `Gigibyte`, defaults to false.
+    ///
+    /// Multiples that are valid with their exact casing are parsed as usual,
+    /// so "KB" is still parsed as `Kibibyte` (unless [`uppercase_kilo`] is
+    /// set). All other casings of "kB", e.g. "kb" or "Kb", are parsed as
+    /// `Kilobyte`. Note that this means that "Mb", which is sometimes used for
+    /// megabits, is parsed as `Megabyte`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, ParseOptions};
+    /// let options = ParseOptions::new().case_insensitive(true);
+    /// let size = Size::parse_with("1 gib", &options).unwrap();
+    /// assert_eq!(size, Size::new(1, Multiple::Gigibyte).unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`uppercase_kilo`]: #method.uppercase_kilo
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> ParseOptions {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Parse a single letter after the value as binary multiple, e.g. "5G" as
+    /// 5 `Gigibyte` and "512K" as 512 `Kibibyte`, defaults to false. This
+    /// matches the sizes printed by the `-h` flag of programs such as `df`
+    /// and `du`. The letters "K", "M", "G", "T" and "P" are supported.
+    ///
+    /// Note that this only applies to bare letters, "KB" is still parsed as
+    /// `Kibibyte` (or `Kilobyte` if [`uppercase_kilo`] is set) and "MB" as
+    /// `Megabyte`. Some programs use "KB" to mean 1000 bytes and "K" to mean
+    /// 1024 bytes, so when mixing both styles check which convention the
+    /// input uses.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::{Size, Multiple, ParseOptions};
+    /// let options = ParseOptions::new().single_letter(true);
+    /// let size = Size::parse_with("5G", &options).unwrap();
+    /// assert_eq!(size, Size::new(5, Multiple::Gigibyte).unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`uppercase_kilo`]: #method.uppercase_kilo
+    pub fn single_letter(mut self, single_letter: bool) -> ParseOptions {
+        self.single_letter = single_letter;
+        self
+    }
+
+    /// Parse `input` as multiple.
+    fn parse_multiple(&self, input: &str) -> Result<Multiple, ParsingError> {
+        match input {
+            "KB" if self.uppercase_kilo => Ok(Multiple::Kilobyte),
+            "K" if self.single_letter => Ok(Multiple::Kibibyte),
+            "M" if self.single_letter => Ok(Multiple::Mebibyte),
+            "G" if self.single_letter => Ok(Multiple::Gigibyte),
+            "T" if self.single_letter => Ok(Multiple::Tebibyte),
+            "P" if self.single_letter => Ok(Multiple::Pebibyte),
+            input => match input.parse() {
+                Err(ParsingError::InvalidMultiple) if self.case_insensitive =>
+                    ParseOptions::parse_multiple_ignore_case(input),
+                result => result,
+            },
+        }
+    }
+
+    /// Parse `input` as multiple, ignoring case.
+    fn parse_multiple_ignore_case(input: &str) -> Result<Multiple, ParsingError> {
+        const SYMBOLS: [(&str, Multiple); 11] = [
+            ("b", Multiple::Byte),
+
+            ("kb", Multiple::Kilobyte),
+            ("mb", Multiple::Megabyte),
+            ("gb", Multiple::Gigabyte),
+            ("tb", Multiple::Terabyte),
+            ("pb", Multiple::Petabyte),
+
+            ("kib", Multiple::Kibibyte),
+            ("mib", Multiple::Mebibyte),
+            ("gib", Multiple::Gigibyte),
+            ("tib", Multiple::Tebibyte),
+            ("pib", Multiple::Pebibyte),
+        ];
+        SYMBOLS.iter()
+            .find(|&&(symbol, _)| symbol.eq_ignore_ascii_case(input))
+            .map(|&(_, multiple)| multiple)
+            .ok_or(ParsingError::InvalidMultiple)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions::new()
+    }
+}
+
+/// A `Multiple` represent a multiple of bytes. This is mainly used to keep track
+/// of what multiple [`Size`] uses, so it can display it using the same multiple
+/// of bytes.
+///
+/// [`Size`]: struct.Size.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::manual_non_exhaustive)]
+pub enum Multiple {
+    /// Represents a single byte, value * 1, "B" when parsing text.
+    Byte,
+
+    /// A kilobyte, value * 1,000 (1000^1), "kB" in when parsing from text.
+    Kilobyte,
+
+    /// A megabyte, value * 1,000,000 (1000^2), "MB" in when parsing from text.
+    Megabyte,
+
+    /// A gigabyte, value * 1,000,000,000 (1000^3), "GB" in when parsing from
+    /// text.
+    Gigabyte,
+
+    /// A terabyte, value * 1,000,000,000,000 (1000^4), "TB" in when parsing
+    /// from text.
+    Terabyte,
+
+    /// A petabyte, value * 1,000,000,000,000,000 (1000^5), "PB" in when
+    /// parsing from text.
+    Petabyte,
+
+    /*
+    /// A exabyte, value * 1,000,000,000,000,000,000 (1000^6), "EB" in when
+    /// parsing from text.
+    Exabyte,
+
+    /// A zettabyte, value * 1,000,000,000,000,000,000,000 (1000^7), "ZB" in
+    /// when parsing from text.
+    Zettabyte,
+
+    /// A yottabyte, value * 1,000,000,000,000,000,000,000,000 (1000^8), "YB"
+    /// in when parsing from text.
+    Yottabyte,
+    */
+
+    /// A kibibyte, value * 1,024 (1024^1), "KiB" or "KB" in when parsing from
+    /// text.
+    Kibibyte,
+
+    /// A mebibyte, value * 1,048,576 (1024^2), "MiB" in when parsing from text.
+    Mebibyte,
+
+    /// A gigibyte, value * 1,073,741,824 (1024^3), "GiB" in when parsing from
+    /// text.
+    Gigibyte,
+
+    /// A tebibyte, value * 1,099,511,627,776 (1024^4), "TiB" in when parsing
+    /// from text.
+    Tebibyte,
+
+    /// A pebibyte, value * 1,125,899,906,842,624 (1024^5), "PiB" in when
+    /// parsing from text.
+    Pebibyte,
+
+    /*
+    /// A exbibyte, value * 1,152,921,504,606,846,976 (1024^6), "EiB" in when
+    /// parsing from text.
+    Exbibyte,
+
+    /// A zebibyte, value * 1,180,591,620,717,411,303,424 (1024^7), "ZiB" in
+    /// when parsing from text.
+    Zebibyte,
+
+    /// A yobibyte, value * 1,208,925,819,614,629,174,706,176 (1024^8), "YiB"
     /// in when parsing from text.
     Yobibyte,
     */
 
-    /// This is not an actual `Multiple`, but allows the enum to be expanded in
-    /// the future without breaking match statements that try to match all
-    /// frame types, because shouldn't be possible anymore.
-    #[doc(hidden)]
-    __NonExhaustive,
-}
+    /// This is not an actual `Multiple`, but allows the enum to be expanded in
+    /// the future without breaking match statements that try to match all
+    /// frame types, because shouldn't be possible anymore.
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// All multiples, from small to large.
+const MULTIPLES: [Multiple; 11] = [
+    Multiple::Byte,
+    Multiple::Kilobyte,
+    Multiple::Kibibyte,
+    Multiple::Megabyte,
+    Multiple::Mebibyte,
+    Multiple::Gigabyte,
+    Multiple::Gigibyte,
+    Multiple::Terabyte,
+    Multiple::Tebibyte,
+    Multiple::Petabyte,
+    Multiple::Pebibyte,
+];
+
+impl Multiple {
+    /// All multiples, ordered by their size in bytes from small to large,
+    /// e.g. to let users pick a multiple. Decimal and binary multiples are
+    /// interleaved: `Byte`, `Kilobyte`, `Kibibyte`, `Megabyte`, etc.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Multiple;
+    /// for multiple in Multiple::all() {
+    ///     println!("{}", multiple); // B, kB, KiB, MB, etc.
+    /// }
+    /// # }
+    /// ```
+    pub fn all() -> &'static [Multiple] {
+        &MULTIPLES
+    }
+
+    /// The base of the multiple, 1000 for decimal multiples, e.g. `Kilobyte`,
+    /// and 1024 for binary multiples, e.g. `Kibibyte`.
+    ///
+    /// `Byte` is considered a decimal multiple, see [`is_decimal`], so it
+    /// returns 1000.
+    ///
+    /// [`is_decimal`]: #method.is_decimal
+    pub fn base(self) -> u32 {
+        if self.is_binary() {
+            1024
+        } else {
+            1000
+        }
+    }
+
+    /// The power of the [`base`] of the multiple, e.g. 2 for `Megabyte`
+    /// (1000^2 bytes) and `Mebibyte` (1024^2 bytes). `Byte` returns 0.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Multiple;
+    /// let multiple = Multiple::Mebibyte;
+    /// assert_eq!(multiple.base(), 1024);
+    /// assert_eq!(multiple.exponent(), 2);
+    /// # }
+    /// ```
+    ///
+    /// [`base`]: #method.base
+    pub fn exponent(self) -> u32 {
+        match self {
+            Multiple::Byte => 0,
+
+            Multiple::Kilobyte | Multiple::Kibibyte => 1,
+            Multiple::Megabyte | Multiple::Mebibyte => 2,
+            Multiple::Gigabyte | Multiple::Gigibyte => 3,
+            Multiple::Terabyte | Multiple::Tebibyte => 4,
+            Multiple::Petabyte | Multiple::Pebibyte => 5,
+
+            Multiple::__NonExhaustive => unreachable!(),
+        }
+    }
+
+    /// Whether or not the multiple is a binary multiple, e.g. `Kibibyte`.
+    /// This returns false for `Byte`, see [`is_decimal`].
+    ///
+    /// [`is_decimal`]: #method.is_decimal
+    pub fn is_binary(self) -> bool {
+        matches!(self, Multiple::Kibibyte | Multiple::Mebibyte |
+            Multiple::Gigibyte | Multiple::Tebibyte | Multiple::Pebibyte)
+    }
+
+    /// Whether or not the multiple is a decimal multiple, e.g. `Kilobyte`.
+    ///
+    /// `Byte` belongs to both families, but to give every multiple a single
+    /// family it's considered decimal: this returns true and [`is_binary`]
+    /// returns false. The other family-aware methods, such as [`base`] and
+    /// [`next_larger`], follow the same convention.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Multiple;
+    /// assert!(Multiple::Megabyte.is_decimal());
+    /// assert!(Multiple::Mebibyte.is_binary());
+    ///
+    /// assert!(Multiple::Byte.is_decimal());
+    /// assert!(!Multiple::Byte.is_binary());
+    /// # }
+    /// ```
+    ///
+    /// [`is_binary`]: #method.is_binary
+    /// [`base`]: #method.base
+    /// [`next_larger`]: #method.next_larger
+    pub fn is_decimal(self) -> bool {
+        !self.is_binary()
+    }
+
+    /// The convention the multiple belongs to, `Byte` is considered decimal.
+    fn convention(self) -> Convention {
+        if self.is_binary() {
+            Convention::Binary
+        } else {
+            Convention::Decimal
+        }
+    }
+
+    /// The next larger multiple in the same family, or `None` for the largest
+    /// multiple. Since `Byte` is considered decimal, see [`is_decimal`], the
+    /// next larger multiple of `Byte` is `Kilobyte`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Multiple;
+    /// assert_eq!(Multiple::Kilobyte.next_larger(), Some(Multiple::Megabyte));
+    /// assert_eq!(Multiple::Kibibyte.next_larger(), Some(Multiple::Mebibyte));
+    /// assert_eq!(Multiple::Pebibyte.next_larger(), None);
+    /// # }
+    /// ```
+    ///
+    /// [`is_decimal`]: #method.is_decimal
+    pub fn next_larger(self) -> Option<Multiple> {
+        let multiples = self.convention().multiples();
+        let index = multiples.iter().position(|m| *m == self)?;
+        multiples.get(index + 1).cloned()
+    }
+
+    /// The next smaller multiple in the same family, or `None` for `Byte`.
+    /// `Byte` is the next smaller multiple of both `Kilobyte` and
+    /// `Kibibyte`.
+    pub fn next_smaller(self) -> Option<Multiple> {
+        let multiples = self.convention().multiples();
+        let index = multiples.iter().position(|m| *m == self)?;
+        index.checked_sub(1).map(|index| multiples[index])
+    }
+
+    /// A compact representation of the multiple as a single byte, for use in
+    /// packed binary formats. See [`from_u8`] to convert it back.
+    ///
+    /// The values are explicitly assigned and won't change in future
+    /// versions, even if multiples are added or reordered. `Byte` is 0,
+    /// decimal multiples are their power of 1000, e.g. 2 for `Megabyte`,
+    /// binary multiples are 16 plus their power of 1024, e.g. 18 for
+    /// `Mebibyte`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Multiple;
+    /// assert_eq!(Multiple::Megabyte.as_u8(), 2);
+    /// assert_eq!(Multiple::Mebibyte.as_u8(), 18);
+    /// # }
+    /// ```
+    ///
+    /// [`from_u8`]: #method.from_u8
+    pub fn as_u8(self) -> u8 {
+        match self {
+            Multiple::Byte => 0,
+
+            Multiple::Kilobyte => 1,
+            Multiple::Megabyte => 2,
+            Multiple::Gigabyte => 3,
+            Multiple::Terabyte => 4,
+            Multiple::Petabyte => 5,
+
+            Multiple::Kibibyte => 17,
+            Multiple::Mebibyte => 18,
+            Multiple::Gigibyte => 19,
+            Multiple::Tebibyte => 20,
+            Multiple::Pebibyte => 21,
+
+            Multiple::__NonExhaustive => unreachable!(),
+        }
+    }
+
+    /// Convert a value returned by [`as_u8`] back into a multiple, returning
+    /// `None` if `value` doesn't represent a multiple.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use human_size::Multiple;
+    /// assert_eq!(Multiple::from_u8(18), Some(Multiple::Mebibyte));
+    /// assert_eq!(Multiple::from_u8(100), None);
+    /// # }
+    /// ```
+    ///
+    /// [`as_u8`]: #method.as_u8
+    pub fn from_u8(value: u8) -> Option<Multiple> {
+        match value {
+            0 => Some(Multiple::Byte),
+
+            1 => Some(Multiple::Kilobyte),
+            2 => Some(Multiple::Megabyte),
+            3 => Some(Multiple::Gigabyte),
+            4 => Some(Multiple::Terabyte),
+            5 => Some(Multiple::Petabyte),
+
+            17 => Some(Multiple::Kibibyte),
+            18 => Some(Multiple::Mebibyte),
+            19 => Some(Multiple::Gigibyte),
+            20 => Some(Multiple::Tebibyte),
+            21 => Some(Multiple::Pebibyte),
+
+            _ => None,
+        }
+    }
+
+    /// The full (singular) name of the multiple, e.g. "kilobyte" for
+    /// `Kilobyte` or "mebibyte" for `Mebibyte`.
+    pub fn long_name(self) -> &'static str {
+        match self {
+            Multiple::Byte => "byte",
+
+            Multiple::Kilobyte => "kilobyte",
+            Multiple::Megabyte => "megabyte",
+            Multiple::Gigabyte => "gigabyte",
+            Multiple::Terabyte => "terabyte",
+            Multiple::Petabyte => "petabyte",
+
+            Multiple::Kibibyte => "kibibyte",
+            Multiple::Mebibyte => "mebibyte",
+            Multiple::Gigibyte => "gibibyte",
+            Multiple::Tebibyte => "tebibyte",
+            Multiple::Pebibyte => "pebibyte",
+
+            Multiple::__NonExhaustive => unreachable!(),
+        }
+    }
+
+    /// Parse the full name of a multiple, see [`long_name`], ignoring case
+    /// and accepting both the singular and plural form.
+    ///
+    /// [`long_name`]: #method.long_name
+    fn from_long_name(input: &str) -> Result<Multiple, ParsingError> {
+        let singular = input.strip_suffix(['s', 'S']).unwrap_or(input);
+        Multiple::all().iter()
+            .find(|multiple| multiple.long_name().eq_ignore_ascii_case(singular))
+            .cloned()
+            .ok_or(ParsingError::InvalidMultiple)
+    }
+
+    /// The symbol of the multiple, e.g. "kB" for `Kilobyte`.
+    fn symbol(self) -> &'static str {
+        match self {
+            Multiple::Byte => "B",
+
+            Multiple::Kilobyte => "kB",
+            Multiple::Megabyte => "MB",
+            Multiple::Gigabyte => "GB",
+            Multiple::Terabyte => "TB",
+            Multiple::Petabyte => "PB",
+            //Multiple::Exabyte => "EB",
+            //Multiple::Zettabyte => "ZB",
+            //Multiple::Yottabyte => "YB",
+
+            Multiple::Kibibyte => "KiB",
+            Multiple::Mebibyte => "MiB",
+            Multiple::Gigibyte => "GiB",
+            Multiple::Tebibyte => "TiB",
+            Multiple::Pebibyte => "PiB",
+            //Multiple::Exbibyte => "EiB",
+            //Multiple::Zebibyte => "ZiB",
+            //Multiple::Yobibyte => "YiB",
+
+            Multiple::__NonExhaustive => unreachable!(),
+        }
+    }
 
-impl Multiple {
     fn multiple_of_bytes(self) -> u64 {
         match self {
             Multiple::Byte => 1,
@@ -269,6 +3158,12 @@ impl Multiple {
     }
 }
 
+/// Parses a multiple from its symbol, e.g. "kB" or "MiB", or its full name,
+/// see [`Multiple::long_name`]. Full names are matched ignoring case and may
+/// be plural, e.g. "Kilobytes". For backwards compatibility "KB" is parsed as
+/// `Kibibyte`.
+///
+/// [`Multiple::long_name`]: enum.Multiple.html#method.long_name
 impl FromStr for Multiple {
     type Err = ParsingError;
 
@@ -294,48 +3189,118 @@ impl FromStr for Multiple {
             //"ZiB" => Ok(Multiple::Zebibyte),
             //"YiB" => Ok(Multiple::Yobibyte),
 
-            _ => Err(ParsingError::InvalidMultiple),
+            input => Multiple::from_long_name(input),
         }
     }
 }
 
 impl fmt::Display for Multiple {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let value = match *self {
-            Multiple::Byte => "B",
+        f.pad(self.symbol())
+    }
+}
 
-            Multiple::Kilobyte => "kB",
-            Multiple::Megabyte => "MB",
-            Multiple::Gigabyte => "GB",
-            Multiple::Terabyte => "TB",
-            Multiple::Petabyte => "PB",
-            //Multiple::Exabyte => "EB",
-            //Multiple::Zettabyte => "ZB",
-            //Multiple::Yottabyte => "YB",
+/// Converts the multiple into its number of bytes, e.g. 1000 for `Kilobyte`.
+impl From<Multiple> for f64 {
+    fn from(multiple: Multiple) -> f64 {
+        multiple.multiple_of_bytes() as f64
+    }
+}
 
-            Multiple::Kibibyte => "KiB",
-            Multiple::Mebibyte => "MiB",
-            Multiple::Gigibyte => "GiB",
-            Multiple::Tebibyte => "TiB",
-            Multiple::Pebibyte => "PiB",
-            //Multiple::Exbibyte => "EiB",
-            //Multiple::Zebibyte => "ZiB",
-            //Multiple::Yobibyte => "YiB",
+/// Converts the multiple into its number of bytes, e.g. 1024 for `Kibibyte`.
+/// This can't fail as every multiple fits in an `u128`.
+impl From<Multiple> for u128 {
+    fn from(multiple: Multiple) -> u128 {
+        u128::from(multiple.multiple_of_bytes())
+    }
+}
 
-            Multiple::__NonExhaustive => unreachable!(),
-        };
-        f.pad(value)
+/// Converts the multiple into its number of bytes as `usize`. Returns an
+/// overflow error if it doesn't fit, which depends on the pointer width of
+/// the target, e.g. `Tebibyte` doesn't fit on 32 bit targets.
+impl TryFrom<Multiple> for usize {
+    type Error = ConversionError;
+
+    fn try_from(multiple: Multiple) -> Result<usize, ConversionError> {
+        usize::try_from(multiple.multiple_of_bytes())
+            .map_err(|_| ConversionError::Overflow)
+    }
+}
+
+/// The convention used for multiples of bytes, either decimal (SI) multiples
+/// of 1000, e.g. `Kilobyte`, or binary (IEC) multiples of 1024, e.g.
+/// `Kibibyte`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Convention {
+    /// Decimal multiples; `Byte`, `Kilobyte`, `Megabyte`, etc.
+    Decimal,
+    /// Binary multiples; `Byte`, `Kibibyte`, `Mebibyte`, etc.
+    Binary,
+}
+
+impl Convention {
+    /// All multiples in the convention, from small to large.
+    fn multiples(self) -> &'static [Multiple] {
+        match self {
+            Convention::Decimal => &[
+                Multiple::Byte,
+                Multiple::Kilobyte,
+                Multiple::Megabyte,
+                Multiple::Gigabyte,
+                Multiple::Terabyte,
+                Multiple::Petabyte,
+            ],
+            Convention::Binary => &[
+                Multiple::Byte,
+                Multiple::Kibibyte,
+                Multiple::Mebibyte,
+                Multiple::Gigibyte,
+                Multiple::Tebibyte,
+                Multiple::Pebibyte,
+            ],
+        }
     }
 }
 
+/// How fractional bytes are rounded when converting a [`Size`] into a whole
+/// number of bytes, see [`Size::to_bytes_rounded`]. The other conversions
+/// into integers, e.g. [`Size::to_integer`] and `TryFrom<Size> for usize`,
+/// truncate.
+///
+/// [`Size`]: struct.Size.html
+/// [`Size::to_bytes_rounded`]: struct.Size.html#method.to_bytes_rounded
+/// [`Size::to_integer`]: struct.Size.html#method.to_integer
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    /// Round down, e.g. 1.5 bytes becomes 1 byte.
+    Floor,
+    /// Round up, e.g. 1.5 bytes becomes 2 bytes.
+    Ceil,
+    /// Round to the nearest byte, half-way cases are rounded up, e.g. 1.5
+    /// bytes becomes 2 bytes and 1.4 bytes becomes 1 byte.
+    Nearest,
+    /// Drop the fractional bytes, e.g. 1.5 bytes becomes 1 byte. As sizes
+    /// are rarely negative this is usually the same as `Floor`.
+    Truncate,
+}
+
 /// The error returned when trying to parse a [`Size`] or [`Mulitple`] from a
-/// string, using the [`FromStr`] trait.
+/// string, using the [`FromStr`] trait. Use [`Size::parse_spanned`] to also
+/// get the position of the error in the string.
 ///
 /// [`Size`]: struct.Size.html
 /// [`Mulitple`]: enum.Multiple.html
 /// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+/// [`Size::parse_spanned`]: struct.Size.html#method.parse_spanned
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ParsingError {
+    /// The provided string is empty or only contains whitespace.
+    EmptyInput,
+    /// The provided string contains non-ASCII or control characters, see
+    /// [`Size::from_ascii_str`].
+    ///
+    /// [`Size::from_ascii_str`]: struct.Size.html#method.from_ascii_str
+    NonAscii,
     /// The provided string is missing a value.
     MissingValue,
     /// The value is invalid.
@@ -344,25 +3309,233 @@ pub enum ParsingError {
     MissingMultiple,
     /// The multiple in the string is invalid.
     InvalidMultiple,
+    /// The throughput is missing the time unit.
+    MissingTimeUnit,
+    /// The time unit of the throughput is invalid.
+    InvalidTimeUnit,
     /// Extra unknown data was provided, e.g. "100 kb extra" here the "extra"
     /// part will cause this error.
     UnknownExtra,
 }
 
+impl ParsingError {
+    /// Description of the error, used by the `Display` implementation.
+    fn as_str(&self) -> &'static str {
+        match *self {
+            ParsingError::EmptyInput => "empty input",
+            ParsingError::NonAscii => "non-ASCII input",
+            ParsingError::MissingValue => "no value",
+            ParsingError::InvalidValue => "invalid value",
+            ParsingError::MissingMultiple => "no multiple",
+            ParsingError::InvalidMultiple => "invalid multiple",
+            ParsingError::MissingTimeUnit => "no time unit",
+            ParsingError::InvalidTimeUnit => "invalid time unit",
+            ParsingError::UnknownExtra => "unknown extra data",
+        }
+    }
+}
+
 impl fmt::Display for ParsingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.pad(self.description())
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for ParsingError {
+    fn description(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// A [`ParsingError`] together with the part of the input that caused it, as
+/// returned by [`Size::parse_spanned`].
+///
+/// The offending token is the sign and value for an invalid value, the
+/// multiple for an invalid multiple and the remainder of the input for
+/// unknown extra data. If a part is missing the token is empty and its
+/// position is where the part was expected.
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::Size;
+/// let input = "5 XB";
+/// let err = Size::parse_spanned(input).unwrap_err();
+/// println!("error: {}", err); // invalid multiple "XB" at position 2
+/// println!("{}", input);
+/// println!("{:>1$}", "^^", err.span().end);
+/// # }
+/// ```
+///
+/// [`ParsingError`]: enum.ParsingError.html
+/// [`Size::parse_spanned`]: struct.Size.html#method.parse_spanned
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SpannedError<'a> {
+    kind: ParsingError,
+    token: &'a str,
+    position: usize,
+}
+
+impl<'a> SpannedError<'a> {
+    /// Create a new error, `token` must be a slice of `input`.
+    fn new(kind: ParsingError, input: &str, token: &'a str) -> SpannedError<'a> {
+        let position = token.as_ptr() as usize - input.as_ptr() as usize;
+        SpannedError { kind, token, position }
+    }
+
+    /// The kind of error.
+    pub fn kind(&self) -> ParsingError {
+        self.kind
+    }
+
+    /// The part of the input that caused the error, empty if a part is
+    /// missing.
+    pub fn token(&self) -> &'a str {
+        self.token
+    }
+
+    /// The byte offset of the [`token`] in the input.
+    ///
+    /// [`token`]: #method.token
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The byte range of the [`token`] in the input.
+    ///
+    /// [`token`]: #method.token
+    pub fn span(&self) -> Range<usize> {
+        self.position..self.position + self.token.len()
+    }
+}
+
+impl<'a> fmt::Display for SpannedError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "{} at position {}", self.kind, self.position)
+        } else {
+            write!(f, "{} {:?} at position {}", self.kind, self.token, self.position)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> StdError for SpannedError<'a> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+impl<'a> From<SpannedError<'a>> for ParsingError {
+    fn from(err: SpannedError<'a>) -> ParsingError {
+        err.kind
+    }
+}
+
+/// The error returned when trying to convert a [`Size`] into an integer.
+///
+/// [`Size`]: struct.Size.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConversionError {
+    /// The size is too large to fit in the integer.
+    Overflow,
+    /// The size is negative, which can't be represented as a number of
+    /// bytes.
+    Negative,
+    /// The value is not a valid number, e.g. NaN or infinity.
+    InvalidValue,
+}
+
+impl ConversionError {
+    /// Description of the error, used by the `Display` implementation.
+    fn as_str(&self) -> &'static str {
+        match *self {
+            ConversionError::Overflow => "size overflows integer",
+            ConversionError::Negative => "negative size",
+            ConversionError::InvalidValue => "invalid value",
+        }
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for ConversionError {
+    fn description(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// The error returned by operations that can fail with either a
+/// [`ParsingError`] or a [`ConversionError`], e.g. parsing a size and
+/// converting it into an integer. Both errors can be converted into `Error`
+/// using the `?` operator.
+///
+/// ```
+/// # extern crate human_size;
+/// use human_size::{Size, Error};
+///
+/// fn parse_bytes(input: &str) -> Result<u64, Error> {
+///     let size: Size = input.parse()?;
+///     let bytes = size.to_integer()?;
+///     Ok(bytes)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(parse_bytes("10 kB"), Ok(10_000));
+/// assert!(parse_bytes("10 XB").is_err());
+/// # }
+/// ```
+///
+/// [`ParsingError`]: enum.ParsingError.html
+/// [`ConversionError`]: enum.ConversionError.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Parsing failed.
+    Parse(ParsingError),
+    /// Conversion failed.
+    Conversion(ConversionError),
+}
+
+impl From<ParsingError> for Error {
+    fn from(err: ParsingError) -> Error {
+        Error::Parse(err)
+    }
+}
+
+impl From<ConversionError> for Error {
+    fn from(err: ConversionError) -> Error {
+        Error::Conversion(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Parse(ref err) => err.fmt(f),
+            Error::Conversion(ref err) => err.fmt(f),
+        }
     }
 }
 
-impl Error for ParsingError {
+#[cfg(feature = "std")]
+impl StdError for Error {
     fn description(&self) -> &str {
         match *self {
-            ParsingError::MissingValue => "no value",
-            ParsingError::InvalidValue => "invalid value",
-            ParsingError::MissingMultiple => "no multiple",
-            ParsingError::InvalidMultiple => "invalid multiple",
-            ParsingError::UnknownExtra => "unknown extra data",
+            Error::Parse(ref err) => err.as_str(),
+            Error::Conversion(ref err) => err.as_str(),
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Error::Parse(ref err) => Some(err),
+            Error::Conversion(ref err) => Some(err),
         }
     }
 }
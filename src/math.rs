@@ -0,0 +1,96 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+//! Float functions that are not available in `core`. With the `std` feature
+//! the functions from `std` are used, otherwise they're implemented here.
+//! `abs` and `copysign` are only available in `core` since Rust 1.85, so
+//! they're always implemented here.
+
+/// Mask of the sign bit of an `f64`.
+const SIGN_MASK: u64 = 1 << 63;
+
+pub fn abs(value: f64) -> f64 {
+    f64::from_bits(value.to_bits() & !SIGN_MASK)
+}
+
+/// Returns `value` with the sign of `sign`.
+#[cfg(not(feature = "std"))]
+pub fn copysign(value: f64, sign: f64) -> f64 {
+    f64::from_bits((value.to_bits() & !SIGN_MASK) | (sign.to_bits() & SIGN_MASK))
+}
+
+#[cfg(feature = "std")]
+pub fn trunc(value: f64) -> f64 {
+    value.trunc()
+}
+
+#[cfg(not(feature = "std"))]
+pub fn trunc(value: f64) -> f64 {
+    // Floats this large don't have a fractional part, this also handles
+    // infinite values.
+    if value.is_nan() || abs(value) >= 4_503_599_627_370_496.0 {
+        value
+    } else {
+        copysign(value as i64 as f64, value)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn floor(value: f64) -> f64 {
+    value.floor()
+}
+
+#[cfg(not(feature = "std"))]
+pub fn floor(value: f64) -> f64 {
+    let truncated = trunc(value);
+    if truncated > value { truncated - 1.0 } else { truncated }
+}
+
+#[cfg(feature = "std")]
+pub fn ceil(value: f64) -> f64 {
+    value.ceil()
+}
+
+#[cfg(not(feature = "std"))]
+pub fn ceil(value: f64) -> f64 {
+    let truncated = trunc(value);
+    if truncated < value { truncated + 1.0 } else { truncated }
+}
+
+#[cfg(feature = "std")]
+pub fn round(value: f64) -> f64 {
+    value.round()
+}
+
+/// Rounds half-way cases away from zero, like `f64::round`.
+#[cfg(not(feature = "std"))]
+pub fn round(value: f64) -> f64 {
+    let truncated = trunc(value);
+    if abs(value - truncated) >= 0.5 {
+        truncated + copysign(1.0, value)
+    } else {
+        truncated
+    }
+}
+
+pub fn fract(value: f64) -> f64 {
+    value - trunc(value)
+}
+
+#[cfg(feature = "std")]
+pub fn powi(value: f64, n: i32) -> f64 {
+    value.powi(n)
+}
+
+#[cfg(not(feature = "std"))]
+pub fn powi(value: f64, n: i32) -> f64 {
+    let mut result = 1.0;
+    for _ in 0..n.unsigned_abs() {
+        result *= value;
+    }
+    if n < 0 { 1.0 / result } else { result }
+}
@@ -0,0 +1,107 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+//! Implementations of serde's `Serialize` and `Deserialize` traits, enabled
+//! using the `serde` feature.
+
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use {Multiple, Size};
+
+/// `Size` is serialized as a string, e.g. "100 kB", using its stored value and
+/// multiple. This means that the multiple a size was created, or parsed, with
+/// is retained, e.g. "1 GiB" is never serialized as "1024 MiB".
+impl Serialize for Size {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// `Size` is deserialized from either a string, using its `FromStr`
+/// implementation, or an integer, which is interpreted as a number of bytes
+/// using [`Size::from_bytes`], e.g. both "1 MB" and 1000000 are deserialized
+/// as one megabyte. Because of this it requires a self-describing format,
+/// such as JSON.
+///
+/// [`Size::from_bytes`]: ../struct.Size.html#method.from_bytes
+impl<'de> Deserialize<'de> for Size {
+    fn deserialize<D>(deserializer: D) -> Result<Size, D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SizeVisitor)
+    }
+}
+
+struct SizeVisitor;
+
+impl<'de> Visitor<'de> for SizeVisitor {
+    type Value = Size;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a size, e.g. \"100 kB\", or a number of bytes")
+    }
+
+    fn visit_str<E>(self, input: &str) -> Result<Size, E>
+        where E: de::Error,
+    {
+        input.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E>(self, bytes: u64) -> Result<Size, E>
+        where E: de::Error,
+    {
+        Ok(Size::from_bytes(u128::from(bytes)))
+    }
+
+    fn visit_u128<E>(self, bytes: u128) -> Result<Size, E>
+        where E: de::Error,
+    {
+        Ok(Size::from_bytes(bytes))
+    }
+}
+
+/// `Multiple` is serialized as its symbol, e.g. "kB", using its `Display`
+/// implementation.
+impl Serialize for Multiple {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// `Multiple` is deserialized from a string using its `FromStr`
+/// implementation. Note that this also accepts "KB" as `Kibibyte`, which is
+/// serialized again as "KiB".
+impl<'de> Deserialize<'de> for Multiple {
+    fn deserialize<D>(deserializer: D) -> Result<Multiple, D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(MultipleVisitor)
+    }
+}
+
+struct MultipleVisitor;
+
+impl<'de> Visitor<'de> for MultipleVisitor {
+    type Value = Multiple;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a multiple, e.g. \"kB\"")
+    }
+
+    fn visit_str<E>(self, input: &str) -> Result<Multiple, E>
+        where E: de::Error,
+    {
+        input.parse().map_err(E::custom)
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+use core::fmt;
+use core::cmp::Ordering;
+use core::str::FromStr;
+use core::time::Duration;
+
+use {Size, ParsingError};
+
+/// `Throughput` represents a rate of bytes, a [`Size`] per interval of time.
+/// `Throughput` can be created using the `new` function, or parsed from a
+/// string using the [`FromStr`] trait, e.g. "10 MB/s".
+///
+/// The supported time units are seconds ("s"), minutes ("min"), hours ("h" or
+/// "hour") and days ("day").
+///
+/// ```
+/// # extern crate human_size;
+/// # fn main() {
+/// use human_size::Throughput;
+/// let throughput = "600 MB/min".parse::<Throughput>().unwrap();
+/// println!("throughput: {}", throughput); // 600 MB/min
+/// println!("bytes per second: {}", throughput.bytes_per_second()); // 10000000
+/// # }
+/// ```
+///
+/// [`Size`]: struct.Size.html
+/// [`FromStr`]: https://doc.rust-lang.org/nightly/core/str/trait.FromStr.html
+#[derive(Copy, Clone, Debug)]
+pub struct Throughput {
+    size: Size,
+    interval: Duration,
+}
+
+impl Throughput {
+    /// Create a new `Throughput` of `size` per `interval`.
+    ///
+    /// ```
+    /// # extern crate human_size;
+    /// # fn main() {
+    /// use std::time::Duration;
+    /// use human_size::{Size, Multiple, Throughput};
+    ///
+    /// let size = Size::new(10, Multiple::Megabyte).unwrap();
+    /// let throughput = Throughput::new(size, Duration::from_secs(1));
+    /// println!("throughput: {}", throughput); // 10 MB/s
+    /// # }
+    /// ```
+    pub fn new(size: Size, interval: Duration) -> Throughput {
+        Throughput { size, interval }
+    }
+
+    /// The number of bytes per second. A zero sized `Throughput` is always
+    /// zero bytes per second, otherwise a zero interval results in an
+    /// infinite number of bytes per second.
+    pub fn bytes_per_second(&self) -> f64 {
+        let bytes = self.size.into_bytes();
+        if bytes == 0.0 {
+            0.0
+        } else {
+            bytes / self.interval.as_secs_f64()
+        }
+    }
+}
+
+impl Eq for Throughput {}
+
+impl PartialEq for Throughput {
+    fn eq(&self, other: &Throughput) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Throughput {
+    fn partial_cmp(&self, other: &Throughput) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Throughputs are compared by their number of bytes per second, see
+/// [`bytes_per_second`]. This means that all zero interval throughputs are
+/// considered equal, and larger than any other throughput (with a size
+/// larger than zero).
+///
+/// [`bytes_per_second`]: #method.bytes_per_second
+impl Ord for Throughput {
+    fn cmp(&self, other: &Throughput) -> Ordering {
+        // Adding zero maps negative zero to (positive) zero.
+        let a = self.bytes_per_second() + 0.0;
+        let b = other.bytes_per_second() + 0.0;
+        a.total_cmp(&b)
+    }
+}
+
+/// The time units used in parsing and displaying `Throughput`, in seconds.
+const TIME_UNITS: [(&str, u64); 4] = [
+    ("s", 1),
+    ("min", 60),
+    ("h", 60 * 60),
+    ("day", 24 * 60 * 60),
+];
+
+impl FromStr for Throughput {
+    type Err = ParsingError;
+
+    fn from_str(input: &str) -> Result<Throughput, Self::Err> {
+        let (size, remainder) = Size::parse_prefix(input)?;
+        let remainder = remainder.trim();
+        if remainder.is_empty() {
+            return Err(ParsingError::MissingTimeUnit);
+        } else if !remainder.starts_with('/') {
+            return Err(ParsingError::UnknownExtra);
+        }
+        let secs = match remainder[1..].trim() {
+            "" => return Err(ParsingError::MissingTimeUnit),
+            "hour" => 60 * 60,
+            unit => TIME_UNITS.iter()
+                .find(|&&(name, _)| name == unit)
+                .map(|&(_, secs)| secs)
+                .ok_or(ParsingError::InvalidTimeUnit)?,
+        };
+        Ok(Throughput::new(size, Duration::from_secs(secs)))
+    }
+}
+
+/// Throughputs are displayed using the time unit of the interval if possible,
+/// e.g. "600 MB/min", other intervals are displayed per second, e.g. 10 MB per
+/// 2 seconds is displayed as "5 MB/s".
+///
+/// A zero interval with a zero size is displayed as zero per second, e.g.
+/// "0 MB/s", matching [`bytes_per_second`]. Any other size per zero interval
+/// is an infinite throughput, which is displayed using the size and a zero
+/// interval, e.g. "10 MB/0s". Note that this can't be parsed.
+///
+/// [`bytes_per_second`]: #method.bytes_per_second
+impl fmt::Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unit = TIME_UNITS.iter()
+            .find(|&&(_, secs)| self.interval == Duration::from_secs(secs));
+        match unit {
+            Some(&(name, _)) => write!(f, "{}/{}", self.size, name),
+            None if self.interval == Duration::from_secs(0) => {
+                if self.size.into_bytes() == 0.0 {
+                    write!(f, "0 {}/s", self.size.multiple)
+                } else {
+                    write!(f, "{}/0s", self.size)
+                }
+            },
+            None => {
+                let secs = self.interval.as_secs_f64();
+                write!(f, "{} {}/s", self.size.value / secs, self.size.multiple)
+            },
+        }
+    }
+}
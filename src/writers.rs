@@ -0,0 +1,159 @@
+// Copyright 2017 Thomas de Zeeuw
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// used, copied, modified, or distributed except according to those terms.
+
+//! `fmt::Write` implementations used to format sizes without allocating.
+
+use core::fmt::{self, Write};
+
+/// Counts the number of characters written.
+pub struct CharCount(pub usize);
+
+impl Write for CharCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Checks if the written number is zero, i.e. it only contains zeros, a sign
+/// and a decimal point.
+pub struct IsZero(pub bool);
+
+impl Write for IsZero {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = self.0 && s.chars().all(|c| c == '0' || c == '-' || c == '.');
+        Ok(())
+    }
+}
+
+/// Checks if the written output equals `expected`.
+pub struct Equals<'a> {
+    expected: &'a str,
+    equal: bool,
+}
+
+impl<'a> Equals<'a> {
+    pub fn new(expected: &'a str) -> Equals<'a> {
+        Equals { expected, equal: true }
+    }
+
+    pub fn is_equal(&self) -> bool {
+        self.equal && self.expected.is_empty()
+    }
+}
+
+impl<'a> Write for Equals<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.expected.strip_prefix(s) {
+            Some(remainder) => self.expected = remainder,
+            None => self.equal = false,
+        }
+        Ok(())
+    }
+}
+
+/// Removes trailing zeros, and a trailing decimal point, from the written
+/// number.
+pub struct TrimZeros<'a, W: ?Sized + 'a> {
+    inner: &'a mut W,
+    fraction: bool,
+    point: bool,
+    zeros: usize,
+}
+
+impl<'a, W: Write + ?Sized> TrimZeros<'a, W> {
+    pub fn new(inner: &'a mut W) -> TrimZeros<'a, W> {
+        TrimZeros { inner, fraction: false, point: false, zeros: 0 }
+    }
+}
+
+impl<'a, W: Write + ?Sized> Write for TrimZeros<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if !self.fraction {
+                if c == '.' {
+                    self.fraction = true;
+                    self.point = true;
+                } else {
+                    self.inner.write_char(c)?;
+                }
+            } else if c == '0' {
+                self.zeros += 1;
+            } else {
+                if self.point {
+                    self.inner.write_char('.')?;
+                    self.point = false;
+                }
+                for _ in 0..self.zeros {
+                    self.inner.write_char('0')?;
+                }
+                self.zeros = 0;
+                self.inner.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Counts the number of digits of the integer part of the written number.
+pub struct IntegerDigits {
+    count: usize,
+    fraction: bool,
+}
+
+impl IntegerDigits {
+    pub fn new() -> IntegerDigits {
+        IntegerDigits { count: 0, fraction: false }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Write for IntegerDigits {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '.' {
+                self.fraction = true;
+            } else if c.is_ascii_digit() && !self.fraction {
+                self.count += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Inserts `separator` between every group of three digits of the integer
+/// part of the written number, which has `digits` digits, see
+/// `IntegerDigits`.
+pub struct GroupDigits<'a, W: ?Sized + 'a> {
+    inner: &'a mut W,
+    separator: char,
+    digits: usize,
+}
+
+impl<'a, W: Write + ?Sized> GroupDigits<'a, W> {
+    pub fn new(inner: &'a mut W, separator: char, digits: usize) -> GroupDigits<'a, W> {
+        GroupDigits { inner, separator, digits }
+    }
+}
+
+impl<'a, W: Write + ?Sized> Write for GroupDigits<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.inner.write_char(c)?;
+            if self.digits > 0 && c.is_ascii_digit() {
+                self.digits -= 1;
+                if self.digits > 0 && self.digits % 3 == 0 {
+                    self.inner.write_char(self.separator)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
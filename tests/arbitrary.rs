@@ -0,0 +1,61 @@
+#![cfg(feature = "arbitrary")]
+
+extern crate arbitrary;
+extern crate human_size;
+
+use arbitrary::{Arbitrary, Unstructured};
+use human_size::*;
+
+#[test]
+fn arbitrary_size_from_fixed_buffer() {
+    let data = [0u8, 5, 1, 9, 2, 0xff, 0xff, 0xff, 0xff, 3, 0, 0, 0, 0, 0, 0, 0xf0, 0x3f, 4];
+    let mut u = Unstructured::new(&data);
+
+    let size = Size::arbitrary(&mut u).unwrap();
+    assert_eq!(size.into_bytes(), 0.0);
+
+    // Must always be valid sizes.
+    let mut u = Unstructured::new(&data);
+    while let Ok(size) = Size::arbitrary(&mut u) {
+        assert!(size.into_bytes() >= 0.0, "size: {:?}", size);
+        assert!(size.to_string().parse::<Size>().is_ok(), "size: {:?}", size);
+        if u.is_empty() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn arbitrary_multiples() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut u = Unstructured::new(&data);
+    let mut seen = Vec::new();
+    while !u.is_empty() {
+        let multiple = Multiple::arbitrary(&mut u).unwrap();
+        if !seen.contains(&multiple) {
+            seen.push(multiple);
+        }
+    }
+    assert_eq!(seen.len(), 11);
+}
+
+#[test]
+fn formatting_arbitrary_sizes() {
+    // Deterministic pseudo random data, using a linear congruential generator.
+    let mut state = 0x2545_f491_u32;
+    let data: Vec<u8> = (0..4096).map(|_| {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (state >> 24) as u8
+    }).collect();
+    let mut u = Unstructured::new(&data);
+
+    for _ in 0..100 {
+        let size = Size::arbitrary(&mut u).unwrap();
+        let _ = size.to_string();
+        let _ = format!("{:#}", size);
+        let _ = format!("{:>20.2}", size);
+        let _ = size.format(&FormatOptions::new().group_separator(','));
+        let _ = size.to_string_long();
+        let _ = size.humanize().to_string();
+    }
+}
@@ -0,0 +1,82 @@
+extern crate human_size;
+
+use human_size::*;
+
+#[test]
+fn should_parse_bit_rate() {
+    let tests = vec![
+        ("100 Mbps", Ok(100_000_000.0)),
+        ("100Mbps", Ok(100_000_000.0)),
+        ("1 Gbps", Ok(1_000_000_000.0)),
+        ("1.5 Gbit/s", Ok(1_500_000_000.0)),
+        ("56 kbps", Ok(56_000.0)),
+        ("56 Kbps", Ok(56_000.0)),
+        ("9600 bps", Ok(9600.0)),
+        ("10 Tbps", Ok(10_000_000_000_000.0)),
+        ("1e3 bps", Ok(1000.0)),
+        ("1_000 kbps", Ok(1_000_000.0)),
+
+        ("", Err(ParsingError::EmptyInput)),
+        ("Mbps", Err(ParsingError::MissingValue)),
+        ("100", Err(ParsingError::MissingMultiple)),
+        ("100 ", Err(ParsingError::MissingMultiple)),
+        ("1.2.3 Mbps", Err(ParsingError::InvalidValue)),
+        // Bytes, not bits.
+        ("100 MB/s", Err(ParsingError::InvalidMultiple)),
+        ("100 MBps", Err(ParsingError::InvalidMultiple)),
+        ("100 Mb", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse::<BitRate>().map(|bit_rate| bit_rate.bits_per_second());
+        assert_eq!(got, test.1, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn converting_bit_rate_to_bytes() {
+    let bit_rate = "100 Mbps".parse::<BitRate>().unwrap();
+    assert_eq!(bit_rate.bytes_per_second(), 12_500_000.0);
+    assert_eq!(bit_rate.to_throughput().bytes_per_second(), 12_500_000.0);
+    assert_eq!(bit_rate.to_throughput(), "12.5 MB/s".parse::<Throughput>().unwrap());
+    assert_eq!(bit_rate.to_throughput().to_string(), "12.5 MB/s");
+    let bit_rate = "80 kbps".parse::<BitRate>().unwrap();
+    assert_eq!(bit_rate.to_throughput().to_string(), "10 kB/s");
+}
+
+#[test]
+fn displaying_bit_rate() {
+    let tests = vec!["100 Mbps", "1.5 Gbps", "56 kbps", "9600 bps", "10 Tbps"];
+
+    for input in tests {
+        let bit_rate = input.parse::<BitRate>().unwrap();
+        assert_eq!(bit_rate.to_string(), input, "input: {:?}", input);
+        assert_eq!(bit_rate.to_string().parse::<BitRate>(), Ok(bit_rate), "input: {:?}", input);
+    }
+
+    assert_eq!("1 Mbit/s".parse::<BitRate>().unwrap().to_string(), "1 Mbps");
+    assert_eq!("1000 kbps".parse::<BitRate>().unwrap(), BitRate::new(1, BitMultiple::Megabit).unwrap());
+}
+
+#[test]
+fn comparing_huge_bit_rates() {
+    use std::cmp::Ordering;
+
+    let max = f64::MAX;
+    let tests = vec![
+        // Both overflow when converted into bits per second.
+        (BitRate::new(max, BitMultiple::Terabit), BitRate::new(max / 2.0, BitMultiple::Terabit), Ordering::Greater),
+        (BitRate::new(max, BitMultiple::Gigabit), BitRate::new(max, BitMultiple::Terabit), Ordering::Less),
+        // Only one overflows.
+        (BitRate::new(max, BitMultiple::Megabit), BitRate::new(max, BitMultiple::Bit), Ordering::Greater),
+        // Regular values.
+        (BitRate::new(1, BitMultiple::Megabit), BitRate::new(1000, BitMultiple::Kilobit), Ordering::Equal),
+        (BitRate::new(0, BitMultiple::Megabit), BitRate::new(-0.0, BitMultiple::Bit), Ordering::Equal),
+    ];
+
+    for test in tests {
+        let (a, b) = (test.0.unwrap(), test.1.unwrap());
+        assert_eq!(a.cmp(&b), test.2, "input: {:?}, {:?}", a, b);
+        assert_eq!(a == b, test.2 == Ordering::Equal, "input: {:?}, {:?}", a, b);
+    }
+}
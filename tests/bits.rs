@@ -0,0 +1,109 @@
+extern crate human_size;
+
+use human_size::*;
+
+#[test]
+fn should_parse_bits() {
+    let tests = vec![
+        ("8 bit", Ok(8.0)),
+        ("1 bits", Ok(1.0)),
+        ("100 Mbit", Ok(100_000_000.0)),
+        ("100Mbit", Ok(100_000_000.0)),
+        ("1.5 kbit", Ok(1500.0)),
+        ("2 Gbit", Ok(2_000_000_000.0)),
+        ("3 Tbit", Ok(3_000_000_000_000.0)),
+        ("1e3 bit", Ok(1000.0)),
+        ("2.5E6 bit", Ok(2_500_000.0)),
+        ("1_000 bit", Ok(1000.0)),
+
+        ("", Err(ParsingError::EmptyInput)),
+        ("Mbit", Err(ParsingError::MissingValue)),
+        ("100", Err(ParsingError::MissingMultiple)),
+        ("1..5 kbit", Err(ParsingError::InvalidValue)),
+        ("1__000 bit", Err(ParsingError::InvalidValue)),
+        ("-1 bit", Err(ParsingError::InvalidValue)),
+        ("100 MB", Err(ParsingError::InvalidMultiple)),
+        ("100 Mbps", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse::<Bits>().map(|bits| bits.into_bits());
+        assert_eq!(got, test.1, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn converting_bits_and_bytes() {
+    let bits = "8 bit".parse::<Bits>().unwrap();
+    assert_eq!(bits.to_size(), "1 B".parse::<Size>().unwrap());
+
+    let bits = "12 bit".parse::<Bits>().unwrap();
+    assert_eq!(bits.to_size().to_string(), "1.5 B");
+
+    let bits = "1 Mbit".parse::<Bits>().unwrap();
+    assert_eq!(bits.to_size(), Size::new(125, Multiple::Kilobyte).unwrap());
+
+    let size = Size::new(1, Multiple::Kibibyte).unwrap();
+    let bits = Bits::from_size(size);
+    assert_eq!(bits.into_bits(), 8192.0);
+    assert_eq!(bits.to_size(), size);
+
+    assert_eq!(Bits::new(1, BitMultiple::Kilobit).unwrap(), "1000 bit".parse::<Bits>().unwrap());
+    assert!(Bits::new(1, BitMultiple::Megabit).unwrap() > Bits::new(999, BitMultiple::Kilobit).unwrap());
+}
+
+#[test]
+fn converting_bits_to_integer() {
+    use std::convert::TryFrom;
+
+    let tests = vec![
+        (Bits::new(8, BitMultiple::Bit), Ok(8)),
+        (Bits::new(100, BitMultiple::Megabit), Ok(100_000_000)),
+        (Bits::new(1.5, BitMultiple::Kilobit), Ok(1500)),
+        (Bits::new(2.5, BitMultiple::Bit), Ok(2)),
+        (Bits::new(0, BitMultiple::Bit), Ok(0)),
+        (Bits::new(-1, BitMultiple::Bit), Err(ConversionError::Negative)),
+        (Bits::new(1e10, BitMultiple::Terabit), Err(ConversionError::Overflow)),
+    ];
+
+    for test in tests {
+        let bits = test.0.unwrap();
+        assert_eq!(bits.to_bits_u64(), test.1, "input: {:?}", bits);
+        assert_eq!(u64::try_from(bits), test.1, "input: {:?}", bits);
+    }
+}
+
+#[test]
+fn displaying_bits() {
+    let tests = vec!["8 bit", "100 Mbit", "1.5 kbit", "2 Gbit", "3 Tbit"];
+
+    for input in tests {
+        let bits = input.parse::<Bits>().unwrap();
+        assert_eq!(bits.to_string(), input, "input: {:?}", input);
+    }
+}
+
+#[test]
+fn comparing_huge_bits() {
+    use std::cmp::Ordering;
+
+    let max = f64::MAX;
+    let tests = vec![
+        // Both overflow when converted into bits.
+        (Bits::new(max, BitMultiple::Terabit), Bits::new(max / 2.0, BitMultiple::Terabit), Ordering::Greater),
+        (Bits::new(max, BitMultiple::Terabit), Bits::new(max, BitMultiple::Terabit), Ordering::Equal),
+        (Bits::new(max, BitMultiple::Gigabit), Bits::new(max, BitMultiple::Terabit), Ordering::Less),
+        // Only one overflows.
+        (Bits::new(max, BitMultiple::Kilobit), Bits::new(max, BitMultiple::Bit), Ordering::Greater),
+        (Bits::new(1, BitMultiple::Bit), Bits::new(max, BitMultiple::Terabit), Ordering::Less),
+        // Regular values.
+        (Bits::new(1, BitMultiple::Kilobit), Bits::new(1000, BitMultiple::Bit), Ordering::Equal),
+        (Bits::new(0, BitMultiple::Kilobit), Bits::new(-0.0, BitMultiple::Bit), Ordering::Equal),
+    ];
+
+    for test in tests {
+        let (a, b) = (test.0.unwrap(), test.1.unwrap());
+        assert_eq!(a.cmp(&b), test.2, "input: {:?}, {:?}", a, b);
+        assert_eq!(a == b, test.2 == Ordering::Equal, "input: {:?}, {:?}", a, b);
+    }
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "clap")]
+
+extern crate clap;
+extern crate human_size;
+
+use clap::error::ErrorKind;
+use clap::{value_parser, Arg, Command};
+use human_size::*;
+
+fn command() -> Command {
+    Command::new("app")
+        .arg(Arg::new("max-size").long("max-size").value_parser(value_parser!(Size)))
+}
+
+#[test]
+fn parsing_size_argument() {
+    let tests = vec![
+        ("10MB", Size::new(10, Multiple::Megabyte)),
+        ("10 MB", Size::new(10, Multiple::Megabyte)),
+        ("1.5 GiB", Size::new(1.5, Multiple::Gigibyte)),
+        ("100 B", Size::new(100, Multiple::Byte)),
+    ];
+
+    for test in tests {
+        let matches = command().try_get_matches_from(["app", "--max-size", test.0]).unwrap();
+        let got = matches.get_one::<Size>("max-size").copied();
+        assert_eq!(got, Some(test.1.unwrap()), "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn parsing_invalid_size_argument() {
+    let tests = vec![
+        ("10 XB", ParsingError::InvalidMultiple),
+        ("10", ParsingError::MissingMultiple),
+        ("MB", ParsingError::MissingValue),
+    ];
+
+    for test in tests {
+        let err = command().try_get_matches_from(["app", "--max-size", test.0]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation, "input: {:?}", test.0);
+        let message = err.to_string();
+        assert!(message.contains(test.0), "input: {:?}, error: {}", test.0, message);
+        assert!(message.contains("--max-size"), "input: {:?}, error: {}", test.0, message);
+        assert!(message.contains(&test.1.to_string()), "input: {:?}, error: {}", test.0, message);
+    }
+}
+
+#[test]
+fn parsing_size_argument_with_options() {
+    let parser = SizeValueParser::with_options(ParseOptions::new().single_letter(true));
+    let cmd = Command::new("app")
+        .arg(Arg::new("max-size").long("max-size").value_parser(parser));
+    let matches = cmd.try_get_matches_from(["app", "--max-size", "5G"]).unwrap();
+    let got = matches.get_one::<Size>("max-size").copied();
+    assert_eq!(got, Some(Size::new(5, Multiple::Gigibyte).unwrap()));
+}
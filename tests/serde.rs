@@ -0,0 +1,106 @@
+#![cfg(feature = "serde")]
+
+extern crate human_size;
+extern crate serde_json;
+
+use human_size::*;
+
+#[test]
+fn serializing_size_retains_multiple() {
+    let tests = vec![
+        "1 GiB",
+        "1024 MiB",
+        "1.5 kB",
+        "0 B",
+        "100 PB",
+        "12 KiB",
+    ];
+
+    for input in tests {
+        let size: Size = input.parse().unwrap();
+        let got = serde_json::to_string(&size).unwrap();
+        assert_eq!(got, format!("\"{}\"", input), "input: {:?}", input);
+
+        let deserialized: Size = serde_json::from_str(&got).unwrap();
+        assert_eq!(deserialized, size, "input: {:?}", input);
+        assert_eq!(deserialized.to_string(), input, "input: {:?}", input);
+    }
+}
+
+#[test]
+fn serde_round_trip() {
+    let size = Size::new(5, Multiple::Mebibyte).unwrap();
+    let serialized = serde_json::to_string(&size).unwrap();
+    assert_eq!(serialized, "\"5 MiB\"");
+    let deserialized: Size = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, size);
+}
+
+#[test]
+fn deserializing_size_from_bytes() {
+    let tests = vec![
+        ("\"1 MB\"", "1000000", "1 MB"),
+        ("\"1500 B\"", "1500", "1500 B"),
+        ("\"0 B\"", "0", "0 B"),
+        ("\"12 kB\"", "12000", "12 kB"),
+        ("\"1024 B\"", "1024", "1024 B"),
+    ];
+
+    for test in tests {
+        let from_str: Size = serde_json::from_str(test.0).unwrap();
+        let from_bytes: Size = serde_json::from_str(test.1).unwrap();
+        assert_eq!(from_str, from_bytes, "input: {:?}", test.1);
+        assert_eq!(from_bytes.to_string(), test.2, "input: {:?}", test.1);
+    }
+
+    let size: Size = serde_json::from_str(&u64::MAX.to_string()).unwrap();
+    assert_eq!(size, Size::from_bytes(u128::from(u64::MAX)));
+
+    let err = serde_json::from_str::<Size>("-1").unwrap_err();
+    assert!(err.to_string().contains("a size"), "error: {}", err);
+}
+
+#[test]
+fn deserializing_invalid_size() {
+    let err = serde_json::from_str::<Size>("\"10 XB\"").unwrap_err();
+    assert!(err.to_string().contains("invalid multiple"), "error: {}", err);
+
+    let err = serde_json::from_str::<Size>("[]").unwrap_err();
+    assert!(err.to_string().contains("a size"), "error: {}", err);
+}
+
+#[test]
+fn serializing_multiple() {
+    let tests = vec![
+        (Multiple::Byte, "B"),
+        (Multiple::Kilobyte, "kB"),
+        (Multiple::Megabyte, "MB"),
+        (Multiple::Gigabyte, "GB"),
+        (Multiple::Terabyte, "TB"),
+        (Multiple::Petabyte, "PB"),
+        (Multiple::Kibibyte, "KiB"),
+        (Multiple::Mebibyte, "MiB"),
+        (Multiple::Gigibyte, "GiB"),
+        (Multiple::Tebibyte, "TiB"),
+        (Multiple::Pebibyte, "PiB"),
+    ];
+
+    for test in tests {
+        let got = serde_json::to_string(&test.0).unwrap();
+        assert_eq!(got, format!("\"{}\"", test.1), "multiple: {:?}", test.0);
+
+        let deserialized: Multiple = serde_json::from_str(&got).unwrap();
+        assert_eq!(deserialized, test.0, "multiple: {:?}", test.0);
+    }
+}
+
+#[test]
+fn deserializing_multiple() {
+    // "KB" is parsed as `Kibibyte`, which is serialized as "KiB".
+    let multiple: Multiple = serde_json::from_str("\"KB\"").unwrap();
+    assert_eq!(multiple, Multiple::Kibibyte);
+    assert_eq!(serde_json::to_string(&multiple).unwrap(), "\"KiB\"");
+
+    let err = serde_json::from_str::<Multiple>("\"XB\"").unwrap_err();
+    assert!(err.to_string().contains("invalid multiple"), "error: {}", err);
+}
@@ -36,12 +36,21 @@ fn should_parse_sizes() {
         ("12MiB", Ok(Size::new(12, Multiple::Mebibyte))),
         ("12MiB ", Ok(Size::new(12, Multiple::Mebibyte))),
 
-        ("", Err(ParsingError::MissingMultiple)),
+        ("8 bit", Ok(Size::new(1, Multiple::Byte))),
+        ("12 bit", Ok(Size::new(1.5, Multiple::Byte))),
+        ("1 bit", Ok(Size::new(0.125, Multiple::Byte))),
+        ("16bits", Ok(Size::new(2, Multiple::Byte))),
+
+        ("", Err(ParsingError::EmptyInput)),
+        ("   ", Err(ParsingError::EmptyInput)),
+        ("\t\n", Err(ParsingError::EmptyInput)),
         ("MB", Err(ParsingError::MissingValue)),
         ("10", Err(ParsingError::MissingMultiple)),
         ("10 abc", Err(ParsingError::InvalidMultiple)),
         (".B", Err(ParsingError::InvalidValue)),
-        ("10 B EXTRA", Err(ParsingError::InvalidMultiple)),
+        ("10 B EXTRA", Err(ParsingError::UnknownExtra)),
+        ("10 MB/s", Err(ParsingError::UnknownExtra)),
+        ("10 ", Err(ParsingError::MissingMultiple)),
     ];
 
     for test in tests {
@@ -54,6 +63,160 @@ fn should_parse_sizes() {
     }
 }
 
+#[test]
+fn parsing_values_with_underscores() {
+    let tests = vec![
+        ("1_000 kB", Ok(Size::new(1000, Multiple::Kilobyte))),
+        ("1_000_000 B", Ok(Size::new(1_000_000, Multiple::Byte))),
+        ("1_0 B", Ok(Size::new(10, Multiple::Byte))),
+        ("1_000.5 MB", Ok(Size::new(1000.5, Multiple::Megabyte))),
+        ("0.000_1 GB", Ok(Size::new(0.0001, Multiple::Gigabyte))),
+        ("1_000MB", Ok(Size::new(1000, Multiple::Megabyte))),
+
+        ("_1000 B", Err(ParsingError::InvalidValue)),
+        ("1000_ B", Err(ParsingError::InvalidValue)),
+        ("1__000 B", Err(ParsingError::InvalidValue)),
+        ("1_.5 B", Err(ParsingError::InvalidValue)),
+        ("1._5 B", Err(ParsingError::InvalidValue)),
+        ("_ B", Err(ParsingError::InvalidValue)),
+        ("1000_MB", Err(ParsingError::InvalidValue)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse();
+        let want = test.1.map(Result::unwrap);
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+
+    let long = format!("1_{} B", "0".repeat(200));
+    assert_eq!(long.parse::<Size>(), Err(ParsingError::InvalidValue));
+    // Without separators long values are still accepted.
+    let long = format!("1{} B", "0".repeat(200));
+    assert_eq!(long.parse::<Size>(), Ok(Size::new(1e200, Multiple::Byte).unwrap()));
+}
+
+#[test]
+fn parsing_scientific_notation() {
+    let tests = vec![
+        ("1e3 B", Ok(Size::new(1000, Multiple::Byte))),
+        ("2.5e6 B", Ok(Size::new(2_500_000, Multiple::Byte))),
+        ("2.5E6 B", Ok(Size::new(2_500_000, Multiple::Byte))),
+        ("1e+3 B", Ok(Size::new(1000, Multiple::Byte))),
+        ("5e-1 kB", Ok(Size::new(0.5, Multiple::Kilobyte))),
+        ("1e3B", Ok(Size::new(1000, Multiple::Byte))),
+        ("1e3 MiB", Ok(Size::new(1000, Multiple::Mebibyte))),
+        ("1_000e3 B", Ok(Size::new(1_000_000, Multiple::Byte))),
+
+        ("1e", Err(ParsingError::InvalidMultiple)),
+        ("1e B", Err(ParsingError::InvalidMultiple)),
+        ("1e+ B", Err(ParsingError::InvalidMultiple)),
+        ("e3 B", Err(ParsingError::MissingValue)),
+        ("1e3", Err(ParsingError::MissingMultiple)),
+        ("1e3.5 B", Err(ParsingError::MissingMultiple)),
+        ("1e400 B", Err(ParsingError::InvalidValue)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse();
+        let want = test.1.map(Result::unwrap);
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+
+    assert_eq!("1e3 B".parse::<Size>(), "1 kB".parse::<Size>());
+    assert_eq!("2.5e6 B".parse::<Size>(), "2.5 MB".parse::<Size>());
+}
+
+#[test]
+fn parsing_full_multiple_names() {
+    let tests = vec![
+        ("5 megabytes", Ok(Size::new(5, Multiple::Megabyte))),
+        ("1 byte", Ok(Size::new(1, Multiple::Byte))),
+        ("0 bytes", Ok(Size::new(0, Multiple::Byte))),
+        ("1 kilobyte", Ok(Size::new(1, Multiple::Kilobyte))),
+        ("1.5 kilobytes", Ok(Size::new(1.5, Multiple::Kilobyte))),
+        ("2 Mebibytes", Ok(Size::new(2, Multiple::Mebibyte))),
+        ("3 GIBIBYTE", Ok(Size::new(3, Multiple::Gigibyte))),
+        ("10 terabytes", Ok(Size::new(10, Multiple::Terabyte))),
+        ("10pebibytes", Ok(Size::new(10, Multiple::Pebibyte))),
+        // Symbols still work as before.
+        ("1 KB", Ok(Size::new(1, Multiple::Kibibyte))),
+        ("1 kB", Ok(Size::new(1, Multiple::Kilobyte))),
+
+        ("1 kilobytess", Err(ParsingError::InvalidMultiple)),
+        ("1 kilo", Err(ParsingError::InvalidMultiple)),
+        ("1 gigibyte", Err(ParsingError::InvalidMultiple)),
+        ("1 s", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse();
+        let want = test.1.map(Result::unwrap);
+        assert_eq!(got, want, "input: {:?}", test.0);
+        if let (Ok(got), Ok(want)) = (got, want) {
+            assert_eq!(got.multiple(), want.multiple(), "input: {:?}", test.0);
+        }
+    }
+
+    for multiple in Multiple::all() {
+        assert_eq!(multiple.long_name().parse(), Ok(*multiple));
+        assert_eq!(Size::new(2, *multiple).unwrap().to_string_long().parse::<Size>().unwrap().multiple(), *multiple);
+    }
+}
+
+#[test]
+fn parsing_size_error_positions() {
+    let tests = vec![
+        ("5 XB", ParsingError::InvalidMultiple, 2, "XB"),
+        ("  5 XB", ParsingError::InvalidMultiple, 4, "XB"),
+        ("5XB", ParsingError::InvalidMultiple, 1, "XB"),
+        ("", ParsingError::EmptyInput, 0, ""),
+        ("   ", ParsingError::EmptyInput, 3, ""),
+        ("MB", ParsingError::MissingValue, 0, ""),
+        (" MB", ParsingError::MissingValue, 1, ""),
+        ("10", ParsingError::MissingMultiple, 2, ""),
+        ("10 ", ParsingError::MissingMultiple, 3, ""),
+        ("10 /s", ParsingError::MissingMultiple, 3, ""),
+        ("1.2.3 MB", ParsingError::InvalidValue, 0, "1.2.3"),
+        ("-5 MB", ParsingError::InvalidValue, 0, "-5"),
+        ("+5 MB", ParsingError::InvalidValue, 0, "+5"),
+        ("10 B extra", ParsingError::UnknownExtra, 5, "extra"),
+        ("10 MB/s ", ParsingError::UnknownExtra, 5, "/s"),
+    ];
+
+    for test in tests {
+        let err = Size::parse_spanned(test.0).unwrap_err();
+        assert_eq!(err.kind(), test.1, "input: {:?}", test.0);
+        assert_eq!(err.position(), test.2, "input: {:?}", test.0);
+        assert_eq!(err.token(), test.3, "input: {:?}", test.0);
+        assert_eq!(&test.0[err.span()], test.3, "input: {:?}", test.0);
+        // Same error as `FromStr`.
+        assert_eq!(test.0.parse::<Size>(), Err(test.1), "input: {:?}", test.0);
+        assert_eq!(ParsingError::from(err), test.1, "input: {:?}", test.0);
+    }
+
+    let size = Size::parse_spanned("5 MB").unwrap();
+    assert_eq!(size, Size::new(5, Multiple::Megabyte).unwrap());
+
+    let err = Size::parse_spanned("5 XB").unwrap_err();
+    assert_eq!(err.to_string(), "invalid multiple \"XB\" at position 2");
+    let err = Size::parse_spanned("10").unwrap_err();
+    assert_eq!(err.to_string(), "no multiple at position 2");
+}
+
+#[test]
+fn display_parse_round_trip() {
+    let values = [0.0, 1.0, 1.5, 0.1, 999.999, 1234.5678, 1e20, 1e-5, 1e-300, f64::MAX, f64::MIN_POSITIVE];
+    for multiple in Multiple::all() {
+        for value in values.iter() {
+            let size = Size::new(*value, *multiple).unwrap();
+            let got: Size = size.to_string().parse().unwrap();
+            assert_eq!(got.value(), size.value(), "input: {:?}", size);
+            assert_eq!(got.multiple(), size.multiple(), "input: {:?}", size);
+            assert_eq!(got, size, "input: {:?}", size);
+        }
+    }
+}
+
 #[test]
 fn displaying_size() {
     let tests = vec![
@@ -66,6 +229,7 @@ fn displaying_size() {
         //(Size::new(100, Multiple::Zettabyte), "100 ZB"),
 
         (Size::new(2, Multiple::Mebibyte), "2 MiB"),
+        (Size::new(1.5, Multiple::Byte), "1.5 B"),
         //(Size::new(3, Multiple::Zebibyte), "3 ZiB"),
         //(Size::new(1000, Multiple::Yobibyte), "1000 YiB"),
         //(Size::new(10.5, Multiple::Yobibyte), "10.5 YiB"),
@@ -122,3 +286,2169 @@ fn size_comparing() {
         assert_eq!(got, want, "input: {:?} and {:?}", test.0, test.1);
     }
 }
+
+#[test]
+fn parsing_bits() {
+    let tests = vec![
+        ("0 bit", 0.0, "0 B"),
+        ("8 bit", 1.0, "1 B"),
+        ("12 bit", 1.5, "1.5 B"),
+        ("4 bits", 0.5, "0.5 B"),
+        ("8192 bits", 1024.0, "1024 B"),
+    ];
+
+    for test in tests {
+        let size: Size = test.0.parse().unwrap();
+        assert_eq!(size.into_bytes(), test.1, "input: {:?}", test.0);
+        assert_eq!(size.to_string(), test.2, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn humanizing_size() {
+    let tests = vec![
+        (Size::new(0, Multiple::Byte), "0 B"),
+        (Size::new(999, Multiple::Byte), "999 B"),
+        (Size::new(1000, Multiple::Byte), "1 kB"),
+        (Size::new(1500, Multiple::Kilobyte), "1.5 MB"),
+        (Size::new(0.5, Multiple::Megabyte), "500 kB"),
+        (Size::new(1_048_576, Multiple::Byte), "1.048576 MB"),
+        (Size::new(2048, Multiple::Kibibyte), "2 MiB"),
+        (Size::new(1000, Multiple::Kibibyte), "1000 KiB"),
+        (Size::new(3, Multiple::Pebibyte), "3 PiB"),
+        (Size::new(5000, Multiple::Petabyte), "5000 PB"),
+    ];
+
+    for test in tests {
+        let got = test.0.unwrap().humanize().to_string();
+        let want = test.1;
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn humanizing_size_with_convention() {
+    let tests = vec![
+        (Size::new(1_048_576, Multiple::Byte), Convention::Binary, "1 MiB"),
+        (Size::new(1_048_576, Multiple::Byte), Convention::Decimal, "1.048576 MB"),
+        (Size::new(1024, Multiple::Kibibyte), Convention::Decimal, "1.048576 MB"),
+        (Size::new(1, Multiple::Megabyte), Convention::Binary, "976.5625 KiB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap().with_convention(test.1);
+        let got = size.humanize();
+        assert_eq!(got.to_string(), test.2, "input: {:?}", size);
+        // Humanizing again must keep the preferred convention.
+        assert_eq!(got.humanize().to_string(), test.2, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn shifting_size_up() {
+    let tests = vec![
+        (Size::new(1000, Multiple::Byte).unwrap(), Some((1.0, Multiple::Kilobyte))),
+        (Size::new(1024, Multiple::Byte).unwrap().with_convention(Convention::Binary),
+            Some((1.0, Multiple::Kibibyte))),
+        (Size::new(5000, Multiple::Kilobyte).unwrap(), Some((5.0, Multiple::Megabyte))),
+        (Size::new(3072, Multiple::Gigibyte).unwrap(), Some((3.0, Multiple::Tebibyte))),
+        (Size::new(0, Multiple::Megabyte).unwrap(), Some((0.0, Multiple::Gigabyte))),
+
+        // Would become fractional.
+        (Size::new(1500, Multiple::Kilobyte).unwrap(), None),
+        (Size::new(1000, Multiple::Kibibyte).unwrap(), None),
+        (Size::new(1024, Multiple::Byte).unwrap(), None),
+        // No larger multiple.
+        (Size::new(1000, Multiple::Petabyte).unwrap(), None),
+        (Size::new(1024, Multiple::Pebibyte).unwrap(), None),
+    ];
+
+    for test in tests {
+        let got = test.0.shift_up();
+        let want = test.1.map(|(value, multiple)| Size::new(value, multiple).unwrap());
+        assert_eq!(got, want, "input: {:?}", test.0);
+        if let Some(got) = got {
+            assert_eq!(got.to_string(), want.unwrap().to_string(), "input: {:?}", test.0);
+        }
+    }
+}
+
+#[test]
+fn shifting_size_down() {
+    let tests = vec![
+        (Size::new(1, Multiple::Kilobyte), Some((1000.0, Multiple::Byte))),
+        (Size::new(1.5, Multiple::Megabyte), Some((1500.0, Multiple::Kilobyte))),
+        (Size::new(1, Multiple::Kibibyte), Some((1024.0, Multiple::Byte))),
+        (Size::new(2, Multiple::Pebibyte), Some((2048.0, Multiple::Tebibyte))),
+
+        // No smaller multiple.
+        (Size::new(1, Multiple::Byte), None),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.shift_down();
+        let want = test.1.map(|(value, multiple)| Size::new(value, multiple).unwrap());
+        assert_eq!(got, want, "input: {:?}", size);
+        if let Some(got) = got {
+            assert_eq!(got.to_string(), want.unwrap().to_string(), "input: {:?}", size);
+        }
+    }
+}
+
+#[test]
+fn parsing_bom_tolerant() {
+    let tests = vec![
+        ("100 kB", Ok(Size::new(100, Multiple::Kilobyte))),
+        ("\u{FEFF}100 kB", Ok(Size::new(100, Multiple::Kilobyte))),
+        ("\u{A0}12 MiB\u{A0}", Ok(Size::new(12, Multiple::Mebibyte))),
+        ("\u{FEFF}\u{A0}12 MiB\u{202F}\n", Ok(Size::new(12, Multiple::Mebibyte))),
+        (" \t1 GB ", Ok(Size::new(1, Multiple::Gigabyte))),
+
+        ("\u{FEFF}", Err(ParsingError::EmptyInput)),
+        ("\u{FEFF}\u{A0}", Err(ParsingError::EmptyInput)),
+        ("\u{FEFF}MB", Err(ParsingError::MissingValue)),
+        ("\u{FEFF}10 abc", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_bom_tolerant(test.0);
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn counting_fitting_items() {
+    let tests = vec![
+        (Size::new(1, Multiple::Gigibyte), Size::new(4, Multiple::Kibibyte), Some(262_144)),
+        (Size::new(1, Multiple::Megabyte), Size::new(1, Multiple::Kilobyte), Some(1000)),
+        (Size::new(1, Multiple::Kibibyte), Size::new(1, Multiple::Kibibyte), Some(1)),
+        // With a remainder.
+        (Size::new(10, Multiple::Byte), Size::new(3, Multiple::Byte), Some(3)),
+        (Size::new(1, Multiple::Megabyte), Size::new(1, Multiple::Kibibyte), Some(976)),
+        (Size::new(1, Multiple::Kilobyte), Size::new(1, Multiple::Kibibyte), Some(0)),
+        (Size::new(0, Multiple::Byte), Size::new(1, Multiple::Byte), Some(0)),
+        // Zero sized item.
+        (Size::new(1, Multiple::Byte), Size::new(0, Multiple::Kilobyte), None),
+        // Too many items.
+        (Ok(Size::MAX), Size::new(1, Multiple::Byte), None),
+        (Size::new(1e300, Multiple::Byte), Size::new(1, Multiple::Byte), None),
+        (Size::new(1e38, Multiple::Byte), Size::new(1, Multiple::Byte), Some(1e38 as u128)),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let item = test.1.unwrap();
+        let got = size.count_fitting(item);
+        assert_eq!(got, test.2, "input: {:?} and {:?}", size, item);
+    }
+}
+
+#[test]
+fn sorting_sizes() {
+    let mut sizes = vec![
+        Size::new(1, Multiple::Mebibyte).unwrap(),
+        Size::new(1, Multiple::Kibibyte).unwrap(),
+        Size::new(1, Multiple::Megabyte).unwrap(),
+        Size::new(0, Multiple::Gigabyte).unwrap(),
+        Size::new(1000, Multiple::Byte).unwrap(),
+        Size::new(1.5, Multiple::Kilobyte).unwrap(),
+        Size::new(1, Multiple::Kilobyte).unwrap(),
+    ];
+
+    sort_sizes(&mut sizes);
+    let got: Vec<f64> = sizes.iter().map(|size| size.into_bytes()).collect();
+    assert_eq!(got, vec![0.0, 1000.0, 1000.0, 1024.0, 1500.0, 1_000_000.0, 1_048_576.0]);
+    // Stable; "1000 B" came before "1 kB".
+    assert_eq!(sizes[1].to_string(), "1000 B");
+    assert_eq!(sizes[2].to_string(), "1 kB");
+
+    sort_sizes_desc(&mut sizes);
+    let got: Vec<f64> = sizes.iter().map(|size| size.into_bytes()).collect();
+    assert_eq!(got, vec![1_048_576.0, 1_000_000.0, 1500.0, 1024.0, 1000.0, 1000.0, 0.0]);
+    assert_eq!(sizes[4].to_string(), "1000 B");
+    assert_eq!(sizes[5].to_string(), "1 kB");
+}
+
+#[test]
+fn displaying_size_rounded_up() {
+    let tests = vec![
+        (Size::new(1.1, Multiple::Gigibyte), Multiple::Gigibyte, "2 GiB"),
+        (Size::new(2, Multiple::Gigibyte), Multiple::Gigabyte, "3 GB"),
+        (Size::new(1000, Multiple::Megabyte), Multiple::Gigabyte, "1 GB"),
+        (Size::new(1001, Multiple::Megabyte), Multiple::Gigabyte, "2 GB"),
+        (Size::new(1, Multiple::Byte), Multiple::Megabyte, "1 MB"),
+        (Size::new(0, Multiple::Byte), Multiple::Megabyte, "0 MB"),
+        (Size::new(1, Multiple::Gigabyte), Multiple::Gigibyte, "1 GiB"),
+        (Size::new(1, Multiple::Gigibyte), Multiple::Gigabyte, "2 GB"),
+        (Size::new(1.5, Multiple::Kilobyte), Multiple::Byte, "1500 B"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.display_rounded_up(test.1);
+        assert_eq!(got, test.2, "input: {:?} in {:?}", size, test.1);
+    }
+}
+
+#[test]
+fn converting_size_to_integer() {
+    let tests = vec![
+        (Size::new(100, Multiple::Byte), Ok(100)),
+        (Size::new(1, Multiple::Kibibyte), Ok(1024)),
+        (Size::new(65_535, Multiple::Byte), Ok(65_535)),
+        (Size::new(1.5, Multiple::Byte), Ok(1)),
+        (Size::new(65_536, Multiple::Byte), Err(ConversionError::Overflow)),
+        (Size::new(1, Multiple::Megabyte), Err(ConversionError::Overflow)),
+        (Size::new(-1, Multiple::Byte), Err(ConversionError::Negative)),
+    ];
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_integer::<u16>(), test.1, "input: {:?}", size);
+    }
+
+    let tests = vec![
+        (Size::new(4, Multiple::Gigibyte), Err(ConversionError::Overflow)),
+        (Size::new(4_294_967_295u32, Multiple::Byte), Ok(4_294_967_295)),
+        (Size::new(1, Multiple::Gigabyte), Ok(1_000_000_000)),
+    ];
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_integer::<u32>(), test.1, "input: {:?}", size);
+    }
+
+    let tests = vec![
+        (Size::new(4, Multiple::Gigibyte), Ok(4_294_967_296)),
+        (Size::new(1, Multiple::Pebibyte), Ok(1_125_899_906_842_624)),
+        (Size::new(20_000, Multiple::Petabyte), Err(ConversionError::Overflow)),
+        (Size::new(1e40, Multiple::Byte), Err(ConversionError::Overflow)),
+    ];
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_integer::<u64>(), test.1, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn creating_sizes_larger_than_u32() {
+    let size = Size::new(5_000_000_000.0, Multiple::Byte).unwrap();
+    assert_eq!(size.value(), 5_000_000_000.0);
+    assert_eq!(size.to_integer::<u64>(), Ok(5_000_000_000u64));
+    assert_eq!(size, Size::from_bytes(5_000_000_000));
+    assert_eq!(size.to_string(), "5000000000 B");
+
+    let size = Size::new(u32::MAX, Multiple::Kilobyte).unwrap();
+    assert_eq!(size.to_integer::<u64>(), Ok(u64::from(u32::MAX) * 1000));
+
+    // Integers up to 2^53 are exact.
+    let bytes = 1u64 << 53;
+    let size = Size::new(bytes as f64, Multiple::Byte).unwrap();
+    assert_eq!(size.to_integer::<u64>(), Ok(bytes));
+    assert_eq!(Size::from_bytes(u128::from(bytes)).to_integer::<u64>(), Ok(bytes));
+}
+
+#[test]
+fn converting_size_to_usize() {
+    use std::convert::TryFrom;
+
+    // Sizes that fit in an `u32` fit on all targets.
+    let tests = vec![
+        (Size::new(100, Multiple::Byte), Ok(100)),
+        (Size::new(1.5, Multiple::Kibibyte), Ok(1536)),
+        (Size::new(4_294_967_295u32, Multiple::Byte), Ok(4_294_967_295)),
+        (Size::new(-1, Multiple::Byte), Err(ConversionError::Negative)),
+        (Size::new(1e40, Multiple::Byte), Err(ConversionError::Overflow)),
+    ];
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(usize::try_from(size), test.1, "input: {:?}", size);
+    }
+
+    let size = Size::new(4, Multiple::Gigibyte).unwrap();
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(usize::try_from(size), Ok(4_294_967_296u64 as usize));
+    } else if cfg!(target_pointer_width = "32") {
+        assert_eq!(usize::try_from(size), Err(ConversionError::Overflow));
+    }
+
+    assert_eq!(usize::try_from(Multiple::Kibibyte), Ok(1024));
+    assert_eq!(usize::try_from(Multiple::Gigabyte), Ok(1_000_000_000));
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(usize::try_from(Multiple::Pebibyte), Ok(1_125_899_906_842_624u64 as usize));
+    } else if cfg!(target_pointer_width = "32") {
+        assert_eq!(usize::try_from(Multiple::Pebibyte), Err(ConversionError::Overflow));
+    }
+}
+
+#[test]
+fn parsing_size_prefix() {
+    let tests = vec![
+        ("10MB/s", Ok((Size::new(10, Multiple::Megabyte), "/s"))),
+        ("10 MB / s", Ok((Size::new(10, Multiple::Megabyte), " / s"))),
+        ("1.5 KiB", Ok((Size::new(1.5, Multiple::Kibibyte), ""))),
+        ("100 kB extra", Ok((Size::new(100, Multiple::Kilobyte), " extra"))),
+        ("16 bit, 2 B", Ok((Size::new(2, Multiple::Byte), ", 2 B"))),
+
+        ("", Err(ParsingError::EmptyInput)),
+        ("/s", Err(ParsingError::MissingValue)),
+        ("10/s", Err(ParsingError::MissingMultiple)),
+        ("10 XB/s", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_prefix(test.0);
+        let want = test.1.map(|(size, remainder)| (size.unwrap(), remainder));
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn formatting_size() {
+    let third_gb = Size::new(1_000_000_000.0 / 3.0, Multiple::Byte).unwrap();
+    let tests = vec![
+        (Size::new(1, Multiple::Byte).unwrap(), FormatOptions::new(), "1 B"),
+        (Size::new(1.5, Multiple::Kilobyte).unwrap(), FormatOptions::new(), "1.5 kB"),
+        (Size::new(0.95367431640625, Multiple::Mebibyte).unwrap(), FormatOptions::new(), "0.953674 MiB"),
+        (third_gb.humanize(), FormatOptions::new(), "333.333333 MB"),
+        (Size::new(1, Multiple::Gigabyte).unwrap().with_convention(Convention::Binary).humanize(),
+            FormatOptions::new(), "953.674316 MiB"),
+        (Size::new(2.0000001, Multiple::Gigabyte).unwrap(), FormatOptions::new(), "2 GB"),
+
+        (third_gb.humanize(), FormatOptions::new().max_precision(2), "333.33 MB"),
+        (third_gb.humanize(), FormatOptions::new().max_precision(0), "333 MB"),
+        (third_gb.humanize(), FormatOptions::new().max_precision(10), "333.3333333333 MB"),
+        (Size::new(1.5, Multiple::Kilobyte).unwrap(), FormatOptions::new().max_precision(0), "2 kB"),
+    ];
+
+    for test in tests {
+        let got = test.0.format(&test.1);
+        assert_eq!(got, test.2, "input: {:?}, options: {:?}", test.0, test.1);
+    }
+}
+
+#[test]
+fn formatting_size_in_multiple() {
+    let tests = vec![
+        (Size::new(1, Multiple::Gigabyte), Multiple::Megabyte, "1000 MB"),
+        (Size::new(512, Multiple::Mebibyte), Multiple::Gigibyte, "0.5 GiB"),
+        (Size::new(1, Multiple::Kibibyte), Multiple::Kilobyte, "1.024 kB"),
+        (Size::new(1, Multiple::Gigabyte), Multiple::Mebibyte, "953.674316 MiB"),
+        (Size::new(1500, Multiple::Byte), Multiple::Kilobyte, "1.5 kB"),
+        (Size::new(3, Multiple::Terabyte), Multiple::Terabyte, "3 TB"),
+        (Size::new(0, Multiple::Petabyte), Multiple::Byte, "0 B"),
+        (Size::new(1, Multiple::Byte), Multiple::Petabyte, "0 PB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.format_in(test.1), test.2, "input: {:?}, {:?}", size, test.1);
+    }
+}
+
+#[test]
+fn formatting_size_with_group_separator() {
+    let comma = FormatOptions::new().group_separator(',');
+    let space = FormatOptions::new().group_separator(' ');
+    let tests = vec![
+        (Size::new(1_234_567, Multiple::Byte), comma, "1,234,567 B"),
+        (Size::new(1_234_567, Multiple::Byte), space, "1 234 567 B"),
+        (Size::new(1000, Multiple::Kilobyte), comma, "1,000 kB"),
+        (Size::new(999, Multiple::Kilobyte), comma, "999 kB"),
+        (Size::new(123_456, Multiple::Byte), comma, "123,456 B"),
+        (Size::new(1234.56789, Multiple::Megabyte), comma, "1,234.56789 MB"),
+        (Size::new(1234.56789, Multiple::Megabyte), comma.max_precision(2), "1,234.57 MB"),
+        (Size::new(-1234, Multiple::Byte), comma, "-1,234 B"),
+        (Size::new(0, Multiple::Byte), comma, "0 B"),
+        (Size::new(1_234_567, Multiple::Byte), FormatOptions::new(), "1234567 B"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.format(&test.1);
+        assert_eq!(got, test.2, "input: {:?}, options: {:?}", size, test.1);
+    }
+
+    // Doesn't change the default output.
+    let size = Size::new(1_234_567, Multiple::Byte).unwrap();
+    assert_eq!(size.to_string(), "1234567 B");
+    let size = Size::new(1_234_567, Multiple::Gigabyte).unwrap().with_convention(Convention::Decimal);
+    assert_eq!(format!("{:#}", size), "1.234567 PB");
+}
+
+#[test]
+fn size_allocation() {
+    let tests = vec![
+        (Size::new(8, Multiple::Kibibyte), Size::new(4, Multiple::Kibibyte), Some((2, 0.0))),
+        (Size::new(10, Multiple::Kibibyte), Size::new(4, Multiple::Kibibyte), Some((3, 2048.0))),
+        (Size::new(1, Multiple::Byte), Size::new(4, Multiple::Kibibyte), Some((1, 4095.0))),
+        (Size::new(1, Multiple::Megabyte), Size::new(512, Multiple::Byte), Some((1954, 448.0))),
+        (Size::new(0, Multiple::Byte), Size::new(4, Multiple::Kibibyte), Some((0, 0.0))),
+        (Size::new(1, Multiple::Kilobyte), Size::new(0, Multiple::Byte), None),
+        (Size::new(1.5, Multiple::Byte), Size::new(4, Multiple::Kibibyte), None),
+        (Size::new(1, Multiple::Kilobyte), Size::new(0.5, Multiple::Byte), None),
+        (Size::new(-1, Multiple::Kilobyte), Size::new(4, Multiple::Kibibyte), None),
+        (Size::new(f64::MAX, Multiple::Pebibyte), Size::new(4, Multiple::Kibibyte), None),
+        // The number of blocks doesn't fit in an `u64`.
+        (Size::new(1e30, Multiple::Byte), Size::new(1, Multiple::Byte), None),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let block = test.1.unwrap();
+        let got = size.allocation(block).map(|(blocks, wasted)| (blocks, wasted.into_bytes()));
+        assert_eq!(got, test.2, "input: {:?} and {:?}", size, block);
+    }
+
+    let size = Size::new(10, Multiple::Kibibyte).unwrap();
+    let block = Size::new(4, Multiple::Kibibyte).unwrap();
+    assert_eq!(size.allocation(block).unwrap().1.to_string(), "2 KiB");
+
+    // Exact above 2^53 bytes, where `f64` can't represent every byte count.
+    let size = Size::new(16, Multiple::Pebibyte).unwrap();
+    let block = Size::new(3, Multiple::Byte).unwrap();
+    assert_eq!(size.allocation(block).map(|(blocks, wasted)| (blocks, wasted.into_bytes())),
+        Some((6_004_799_503_160_662, 2.0)));
+}
+
+#[test]
+fn converting_into_error() {
+    fn parse_bytes(input: &str) -> Result<u16, Error> {
+        let size: Size = input.parse()?;
+        let bytes = size.to_integer()?;
+        Ok(bytes)
+    }
+
+    let tests = vec![
+        ("1 kB", Ok(1000)),
+        ("10 XB", Err(Error::Parse(ParsingError::InvalidMultiple))),
+        ("", Err(Error::Parse(ParsingError::EmptyInput))),
+        ("1 MB", Err(Error::Conversion(ConversionError::Overflow))),
+    ];
+
+    for test in tests {
+        let got = parse_bytes(test.0);
+        assert_eq!(got, test.1, "input: {:?}", test.0);
+    }
+
+    let err = parse_bytes("10 XB").unwrap_err();
+    assert_eq!(err.to_string(), "invalid multiple");
+    #[cfg(feature = "std")]
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn humanizing_size_capped() {
+    let tests = vec![
+        (Size::new(4, Multiple::Tebibyte), Multiple::Gigibyte, Convention::Binary, "4096 GiB"),
+        (Size::new(3, Multiple::Petabyte), Multiple::Gigabyte, Convention::Decimal, "3000000 GB"),
+        (Size::new(4, Multiple::Terabyte), Multiple::Gigibyte, Convention::Binary, "3725.290298461914 GiB"),
+        // Small sizes are not affected by the cap.
+        (Size::new(1500, Multiple::Kilobyte), Multiple::Gigabyte, Convention::Decimal, "1.5 MB"),
+        (Size::new(512, Multiple::Byte), Multiple::Gigibyte, Convention::Binary, "512 B"),
+        (Size::new(2048, Multiple::Byte), Multiple::Byte, Convention::Binary, "2048 B"),
+        // The cap may be of the other convention.
+        (Size::new(2, Multiple::Gigibyte), Multiple::Gigibyte, Convention::Decimal, "2.147483648 GB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.humanize_capped(test.1, test.2).to_string();
+        assert_eq!(got, test.3, "input: {:?}, {:?}, {:?}", size, test.1, test.2);
+    }
+}
+
+#[test]
+fn parsing_comma_decimal_separator() {
+    let comma = LocaleOptions::new().decimal_separator(',');
+    let tests = vec![
+        ("1,5 GiB", "1.5 GiB"),
+        ("0,25 MB", "0.25 MB"),
+        ("1000,125 kB", "1000.125 kB"),
+        ("12 B", "12 B"),
+    ];
+
+    for test in tests {
+        let got = Size::parse_localized(test.0, &comma).unwrap();
+        let want: Size = test.1.parse().unwrap();
+        assert_eq!(got, want, "input: {:?}", test.0);
+        assert_eq!(got.value(), want.value(), "input: {:?}", test.0);
+        assert_eq!(got.multiple(), want.multiple(), "input: {:?}", test.0);
+    }
+
+    // Periods aren't used by the locale, so they're rejected.
+    assert_eq!(Size::parse_localized("1.5 GiB", &comma), Err(ParsingError::InvalidValue));
+    assert_eq!(Size::parse_localized("1.500,5 GiB", &comma), Err(ParsingError::InvalidValue));
+    // Using the same group separator doesn't make the comma ambiguous.
+    let same = comma.group_separator(',');
+    assert_eq!(Size::parse_localized("1,5 GiB", &same), Ok(Size::new(1.5, Multiple::Gigibyte).unwrap()));
+}
+
+#[test]
+fn parsing_localized_sizes() {
+    let us = LocaleOptions::new().group_separator(',');
+    let european = LocaleOptions::new().decimal_separator(',').group_separator(' ');
+    let german = LocaleOptions::new().decimal_separator(',').group_separator('.');
+    let thin_space = LocaleOptions::new().decimal_separator(',').group_separator('\u{2009}');
+
+    let tests = vec![
+        ("1,234.5 MB", us, Ok(Size::new(1234.5, Multiple::Megabyte))),
+        ("1 234,5 MB", european, Ok(Size::new(1234.5, Multiple::Megabyte))),
+        ("1.234,5 MB", german, Ok(Size::new(1234.5, Multiple::Megabyte))),
+        ("1\u{2009}234,5 MB", thin_space, Ok(Size::new(1234.5, Multiple::Megabyte))),
+        ("1,234,567 B", us, Ok(Size::new(1_234_567, Multiple::Byte))),
+        ("1 234 567 B", european, Ok(Size::new(1_234_567, Multiple::Byte))),
+        ("12 MB", european, Ok(Size::new(12, Multiple::Megabyte))),
+        ("12MB", european, Ok(Size::new(12, Multiple::Megabyte))),
+        ("1234.5 MB", us, Ok(Size::new(1234.5, Multiple::Megabyte))),
+        ("1234,5 MB", european, Ok(Size::new(1234.5, Multiple::Megabyte))),
+        ("0,5 GiB", european, Ok(Size::new(0.5, Multiple::Gigibyte))),
+        ("1.5 GiB", LocaleOptions::new(), Ok(Size::new(1.5, Multiple::Gigibyte))),
+
+        // Incorrectly grouped.
+        ("1,23,456 B", us, Err(ParsingError::InvalidValue)),
+        ("1234,567 B", us, Err(ParsingError::InvalidValue)),
+        ("1,2345 B", us, Err(ParsingError::InvalidValue)),
+        ("1,234,5 MB", european, Err(ParsingError::InvalidValue)),
+        ("1.5 MB", european, Err(ParsingError::InvalidValue)),
+
+        ("", us, Err(ParsingError::EmptyInput)),
+        ("MB", european, Err(ParsingError::MissingValue)),
+        ("1,5", european, Err(ParsingError::MissingMultiple)),
+        ("1,5 XB", european, Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_localized(test.0, &test.1);
+        let want = match test.2 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}, locale: {:?}", test.0, test.1);
+    }
+}
+
+#[test]
+fn decomposing_and_folding_size() {
+    let tests = vec![
+        (Size::new(1_234_567, Multiple::Byte), Convention::Decimal, vec!["1 MB", "234 kB", "567 B"]),
+        (Size::new(1.5, Multiple::Gigibyte), Convention::Binary, vec!["1 GiB", "512 MiB"]),
+        (Size::new(1_610_612_739, Multiple::Byte), Convention::Binary, vec!["1 GiB", "512 MiB", "3 B"]),
+        (Size::new(2, Multiple::Petabyte), Convention::Decimal, vec!["2 PB"]),
+        (Size::new(1000, Multiple::Byte), Convention::Binary, vec!["1000 B"]),
+        (Size::new(1.5, Multiple::Byte), Convention::Decimal, vec!["1.5 B"]),
+        (Size::new(0, Multiple::Kilobyte), Convention::Decimal, vec!["0 B"]),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let parts = size.decompose(test.1);
+        let got: Vec<String> = parts.iter().map(|part| part.to_string()).collect();
+        assert_eq!(got, test.2, "input: {:?}, {:?}", size, test.1);
+
+        let folded = Size::fold_units(&parts).unwrap();
+        assert_eq!(folded.into_bytes(), size.into_bytes(), "input: {:?}, {:?}", size, test.1);
+    }
+
+    assert_eq!(Size::fold_units(&[]).unwrap().to_string(), "0 B");
+    let parts = [Size::new(1, Multiple::Gigibyte).unwrap(), Size::new(512, Multiple::Mebibyte).unwrap()];
+    assert_eq!(Size::fold_units(&parts).unwrap().to_string(), "1.5 GiB");
+    let parts = [Size::new(1e308, Multiple::Byte).unwrap(), Size::new(1e308, Multiple::Byte).unwrap()];
+    assert_eq!(Size::fold_units(&parts), Err(ConversionError::Overflow));
+}
+
+#[test]
+fn uppercase_kilo() {
+    let size = Size::new(10, Multiple::Kilobyte).unwrap();
+    let format_options = FormatOptions::new().uppercase_kilo(true);
+    let parse_options = ParseOptions::new().uppercase_kilo(true);
+
+    let formatted = size.format(&format_options);
+    assert_eq!(formatted, "10 KB");
+    assert_eq!(size.format(&FormatOptions::new()), "10 kB");
+    // Other multiples are not affected.
+    let mebibyte = Size::new(1, Multiple::Mebibyte).unwrap();
+    assert_eq!(mebibyte.format(&format_options), "1 MiB");
+
+    // Round trip using matching options.
+    let parsed = Size::parse_with(&formatted, &parse_options).unwrap();
+    assert_eq!(parsed, size);
+    assert_eq!(parsed.to_string(), "10 kB");
+    // "kB" still works.
+    assert_eq!(Size::parse_with("10 kB", &parse_options), Ok(size));
+
+    // Without the option "KB" is a kibibyte.
+    let parsed = Size::parse_with(&formatted, &ParseOptions::new()).unwrap();
+    assert_eq!(parsed.to_string(), "10 KiB");
+}
+
+#[test]
+fn size_from_f64_bytes() {
+    use std::f64;
+
+    let tests = vec![
+        (1234.5, Ok("1234.5 B")),
+        (0.0, Ok("0 B")),
+        (0.5, Ok("0.5 B")),
+        (1e9, Ok("1000000000 B")),
+
+        (-1.0, Err(ConversionError::Negative)),
+        (f64::NEG_INFINITY, Err(ConversionError::Negative)),
+        (f64::NAN, Err(ConversionError::InvalidValue)),
+        (f64::INFINITY, Err(ConversionError::InvalidValue)),
+        (f64::MIN_POSITIVE / 2.0, Err(ConversionError::InvalidValue)),
+    ];
+
+    for test in tests {
+        let got = Size::try_from_f64_bytes(test.0).map(|size| size.to_string());
+        let want = test.1.map(|size| size.to_owned());
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn describing_size_versus_references() {
+    let references = [
+        ("floppy", Size::new(1.44, Multiple::Megabyte).unwrap()),
+        ("CD", Size::new(700, Multiple::Megabyte).unwrap()),
+        ("DVD", Size::new(4, Multiple::Gigabyte).unwrap()),
+    ];
+
+    let size = Size::new(1.4, Multiple::Gigabyte).unwrap();
+    let got = size.describe_vs_references(&references);
+    let labels: Vec<&str> = got.iter().map(|&(label, _)| label).collect();
+    assert_eq!(labels, vec!["floppy", "CD", "DVD"]);
+    assert!((got[0].1 - 972.2222222222222).abs() < 1e-9);
+    assert_eq!(got[1].1, 2.0);
+    assert_eq!(got[2].1, 0.35);
+
+    assert!(size.describe_vs_references(&[]).is_empty());
+}
+
+#[test]
+fn parsing_after_eq() {
+    let tests = vec![
+        ("maxsize=10MB", Ok(Size::new(10, Multiple::Megabyte))),
+        ("size=1.5 GiB", Ok(Size::new(1.5, Multiple::Gigibyte))),
+        ("a=b=100 B", Ok(Size::new(100, Multiple::Byte))),
+        ("=1 kB", Ok(Size::new(1, Multiple::Kilobyte))),
+        // No `=`.
+        ("10MB", Ok(Size::new(10, Multiple::Megabyte))),
+
+        ("maxsize=", Err(ParsingError::EmptyInput)),
+        ("maxsize= ", Err(ParsingError::EmptyInput)),
+        ("maxsize", Err(ParsingError::MissingValue)),
+        ("maxsize=10", Err(ParsingError::MissingMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_after_eq(test.0);
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn widening_size_until_integer() {
+    let tests = vec![
+        (Size::new(0.5, Multiple::Gigibyte), "512 MiB"),
+        (Size::new(1.5, Multiple::Megabyte), "1500 kB"),
+        (Size::new(0.25, Multiple::Kibibyte), "256 B"),
+        (Size::new(0.0001, Multiple::Terabyte), "100 MB"),
+        (Size::new(2, Multiple::Gigabyte), "2 GB"),
+        (Size::new(0, Multiple::Petabyte), "0 PB"),
+        // Not a whole number of bytes.
+        (Size::new(1.5, Multiple::Byte), "1.5 B"),
+        (Size::new(0.0001, Multiple::Kilobyte), "0.1 B"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.widen_until_integer();
+        assert_eq!(got.to_string(), test.1, "input: {:?}", size);
+        assert_eq!(got, size, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn converting_size() {
+    let tests = vec![
+        (Size::new(1, Multiple::Gigabyte), Multiple::Megabyte, Some((1000.0, Multiple::Megabyte))),
+        (Size::new(1500, Multiple::Kilobyte), Multiple::Megabyte, Some((1.5, Multiple::Megabyte))),
+        (Size::new(1, Multiple::Mebibyte), Multiple::Kilobyte, Some((1048.576, Multiple::Kilobyte))),
+        (Size::new(512, Multiple::Mebibyte), Multiple::Gigibyte, Some((0.5, Multiple::Gigibyte))),
+        (Size::new(3, Multiple::Byte), Multiple::Byte, Some((3.0, Multiple::Byte))),
+
+        (Size::new(1e308, Multiple::Pebibyte), Multiple::Byte, None),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.convert(test.1);
+        let want = test.2.map(|(value, multiple)| Size::new(value, multiple).unwrap());
+        assert_eq!(got, want, "input: {:?}", size);
+        if let (Some(got), Some(want)) = (got, want) {
+            assert_eq!(got.multiple(), want.multiple(), "input: {:?}", size);
+            assert_eq!(got.value(), want.value(), "input: {:?}", size);
+        }
+    }
+}
+
+#[test]
+fn normalizing_size() {
+    let tests = vec![
+        (Size::new(5000, Multiple::Kilobyte), "5 MB"),
+        (Size::new(1500, Multiple::Kilobyte), "1500 kB"),
+        (Size::new(2_000_000, Multiple::Byte), "2 MB"),
+        (Size::new(2048, Multiple::Mebibyte), "2 GiB"),
+        (Size::new(0.5, Multiple::Gigibyte), "512 MiB"),
+        (Size::new(3, Multiple::Terabyte), "3 TB"),
+        (Size::new(0, Multiple::Kilobyte), "0 kB"),
+        (Size::new(1.5, Multiple::Byte), "1.5 B"),
+        // Doesn't cross between the decimal and binary families.
+        (Size::new(1024, Multiple::Kilobyte), "1024 kB"),
+        (Size::new(1000, Multiple::Kibibyte), "1000 KiB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.normalize();
+        assert_eq!(got.to_string(), test.1, "input: {:?}", size);
+        assert_eq!(got, size, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn checked_scaling_size_by_ratio() {
+    let tests = vec![
+        (Size::new(3, Multiple::Gigabyte), 2, 3, Some((2.0, Multiple::Gigabyte))),
+        (Size::new(1, Multiple::Kibibyte), 1, 2, Some((0.5, Multiple::Kibibyte))),
+        (Size::new(10, Multiple::Byte), 0, 7, Some((0.0, Multiple::Byte))),
+        // A naive multiply of the bytes (as `u128`) would overflow.
+        (Size::new(1_000_000, Multiple::Pebibyte), u64::MAX, u64::MAX, Some((1_000_000.0, Multiple::Pebibyte))),
+        (Size::new(1_000_000, Multiple::Pebibyte), u64::MAX, 1, Some((1_000_000.0 * u64::MAX as f64, Multiple::Pebibyte))),
+        // `value * numerator` overflows, but the result fits.
+        (Size::new(1e300, Multiple::Byte), 1_000_000_000, 1_000_000_000_000, Some((1e300 / 1e12 * 1e9, Multiple::Byte))),
+
+        (Size::new(1, Multiple::Byte), 1, 0, None),
+        (Size::new(1e308, Multiple::Byte), 10, 1, None),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.checked_scale_ratio(test.1, test.2);
+        let want = test.3.map(|(value, multiple)| Size::new(value, multiple).unwrap());
+        assert_eq!(got, want, "input: {:?} * {} / {}", size, test.1, test.2);
+        if let (Some(got), Some(want)) = (got, want) {
+            assert_eq!(got.to_string(), want.to_string(), "input: {:?} * {} / {}", size, test.1, test.2);
+        }
+    }
+}
+
+#[test]
+fn size_multiple() {
+    let size: Size = "1 GiB".parse().unwrap();
+    assert_eq!(size.multiple(), Multiple::Gigibyte);
+
+    let other = Size::new(1024, Multiple::Mebibyte).unwrap();
+    assert_eq!(size, other);
+    assert!(size <= other);
+    assert_eq!(size.multiple(), Multiple::Gigibyte);
+    assert_eq!(other.multiple(), Multiple::Mebibyte);
+
+    // Operations returning a new size don't change the original.
+    let _ = size.humanize();
+    let _ = size.shift_down();
+    assert_eq!(size.multiple(), Multiple::Gigibyte);
+
+    let tests = vec![
+        ("100 B", Multiple::Byte),
+        ("1 kB", Multiple::Kilobyte),
+        ("1 KB", Multiple::Kibibyte),
+        ("1.5 PiB", Multiple::Pebibyte),
+        ("16 bit", Multiple::Byte),
+    ];
+    for test in tests {
+        let size: Size = test.0.parse().unwrap();
+        assert_eq!(size.multiple(), test.1, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn parsing_ascii_only() {
+    let tests = vec![
+        ("10 MB", Ok(Size::new(10, Multiple::Megabyte))),
+        ("1.5KiB", Ok(Size::new(1.5, Multiple::Kibibyte))),
+        ("12 MiB ", Ok(Size::new(12, Multiple::Mebibyte))),
+
+        // Arabic-Indic digit three.
+        ("\u{663} MB", Err(ParsingError::NonAscii)),
+        // Cyrillic "М" and "В".
+        ("10 \u{41C}\u{412}", Err(ParsingError::NonAscii)),
+        // Fullwidth "1".
+        ("\u{FF11} kB", Err(ParsingError::NonAscii)),
+        ("10\u{A0}MB", Err(ParsingError::NonAscii)),
+        ("12\tMiB", Err(ParsingError::NonAscii)),
+        ("12 MiB\0", Err(ParsingError::NonAscii)),
+
+        ("", Err(ParsingError::EmptyInput)),
+        ("10 XB", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::from_ascii_str(test.0);
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn approx_label() {
+    let tests = vec![
+        (Size::new(0, Multiple::Byte), Convention::Decimal, "< 1 kB"),
+        (Size::new(999, Multiple::Byte), Convention::Decimal, "< 1 kB"),
+        (Size::new(1000, Multiple::Byte), Convention::Decimal, "a few kB"),
+        (Size::new(1000, Multiple::Byte), Convention::Binary, "< 1 KiB"),
+        (Size::new(3, Multiple::Megabyte), Convention::Decimal, "a few MB"),
+        (Size::new(9.9, Multiple::Megabyte), Convention::Decimal, "a few MB"),
+        (Size::new(10, Multiple::Megabyte), Convention::Decimal, "tens of MB"),
+        (Size::new(42, Multiple::Gigabyte), Convention::Decimal, "tens of GB"),
+        (Size::new(999, Multiple::Gigabyte), Convention::Decimal, "hundreds of GB"),
+        (Size::new(1000, Multiple::Gigabyte), Convention::Decimal, "a few TB"),
+        (Size::new(500, Multiple::Tebibyte), Convention::Binary, "hundreds of TiB"),
+        (Size::new(1000, Multiple::Kibibyte), Convention::Binary, "thousands of KiB"),
+        (Size::new(1, Multiple::Mebibyte), Convention::Binary, "a few MiB"),
+        (Size::new(1, Multiple::Mebibyte), Convention::Decimal, "a few MB"),
+        (Size::new(20_000, Multiple::Petabyte), Convention::Decimal, "thousands of PB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.approx_label(test.1), test.2, "size: {:?}, convention: {:?}", size, test.1);
+    }
+}
+
+#[test]
+fn combining_sizes() {
+    use std::cmp;
+
+    fn gcd(a: u128, b: u128) -> u128 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    type Op = fn(u128, u128) -> u128;
+
+    let tests: Vec<(Size, Size, Op, Result<Size, ConversionError>)> = vec![
+        (Size::new(1, Multiple::Megabyte).unwrap(), Size::new(800, Multiple::Kilobyte).unwrap(), cmp::max, Ok(Size::new(1, Multiple::Megabyte).unwrap())),
+        (Size::new(1, Multiple::Mebibyte).unwrap(), Size::new(1, Multiple::Megabyte).unwrap(), cmp::max, Ok(Size::new(1.048576, Multiple::Megabyte).unwrap())),
+        (Size::new(1, Multiple::Mebibyte).unwrap(), Size::new(1, Multiple::Megabyte).unwrap(), cmp::min, Ok(Size::new(1, Multiple::Megabyte).unwrap())),
+        (Size::new(12, Multiple::Kibibyte).unwrap(), Size::new(8, Multiple::Kibibyte).unwrap(), gcd, Ok(Size::new(4.096, Multiple::Kilobyte).unwrap())),
+        (Size::new(6, Multiple::Kilobyte).unwrap(), Size::new(4, Multiple::Kilobyte).unwrap(), |a, b| a.saturating_sub(b), Ok(Size::new(2, Multiple::Kilobyte).unwrap())),
+        (Size::new(-1, Multiple::Kilobyte).unwrap(), Size::new(4, Multiple::Kilobyte).unwrap(), cmp::max, Err(ConversionError::Negative)),
+        (Size::new(1e30, Multiple::Petabyte).unwrap(), Size::new(4, Multiple::Kilobyte).unwrap(), cmp::max, Err(ConversionError::Overflow)),
+    ];
+
+    for test in tests {
+        let got = test.0.combine_with(&test.1, test.2);
+        assert_eq!(got, test.3, "input: {:?}, {:?}", test.0, test.1);
+        if let Ok(got) = got {
+            assert_eq!(got.multiple(), test.3.unwrap().multiple(), "input: {:?}, {:?}", test.0, test.1);
+        }
+    }
+}
+
+#[test]
+fn parsing_windows_dir_output() {
+    let tests = vec![
+        ("1,048,576 bytes", Some(Size::new(1_048_576, Multiple::Byte))),
+        ("512 bytes", Some(Size::new(512, Multiple::Byte))),
+        ("0 Bytes", Some(Size::new(0, Multiple::Byte))),
+        ("               3 File(s)      1,234,567 bytes", Some(Size::new(1_234_567, Multiple::Byte))),
+        ("               2 Dir(s)  98,765,432,100 bytes free", Some(Size::new(98_765_432_100.0, Multiple::Byte))),
+
+        ("10/14/2026  09:00 AM    <DIR>          src", None),
+        ("1,048,576", None),
+        ("bytes", None),
+        ("1.5 bytes", None),
+        ("1,04,8576 bytes", None),
+        ("", None),
+    ];
+
+    for test in tests {
+        let got = Size::from_windows_dir(test.0);
+        let want = test.1.map(|size| size.unwrap());
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn formatting_ranges() {
+    let tests = vec![
+        (Size::new(1.2, Multiple::Gigabyte), Size::new(3.4, Multiple::Gigabyte), Convention::Decimal, "1.2–3.4 GB"),
+        (Size::new(500, Multiple::Megabyte), Size::new(2, Multiple::Gigabyte), Convention::Decimal, "0.5–2 GB"),
+        (Size::new(10, Multiple::Byte), Size::new(900, Multiple::Byte), Convention::Decimal, "10–900 B"),
+        (Size::new(100, Multiple::Kilobyte), Size::new(1, Multiple::Megabyte), Convention::Decimal, "0.1–1 MB"),
+        (Size::new(512, Multiple::Kibibyte), Size::new(1.5, Multiple::Mebibyte), Convention::Binary, "0.5–1.5 MiB"),
+        (Size::new(1, Multiple::Mebibyte), Size::new(2, Multiple::Megabyte), Convention::Decimal, "1.048576–2 MB"),
+        (Size::new(2, Multiple::Gigabyte), Size::new(500, Multiple::Megabyte), Convention::Decimal, "2–0.5 GB"),
+    ];
+
+    for test in tests {
+        let (lo, hi) = (test.0.unwrap(), test.1.unwrap());
+        assert_eq!(format_range(lo, hi, test.2), test.3, "input: {:?}, {:?}", lo, hi);
+    }
+}
+
+#[test]
+fn coarsening_size() {
+    let tests = vec![
+        (Size::new(1_234_567, Multiple::Byte), Multiple::Kilobyte, 1_234_000.0),
+        (Size::new(1_234_567, Multiple::Byte), Multiple::Kibibyte, 1_233_920.0),
+        (Size::new(1_234_567, Multiple::Byte), Multiple::Megabyte, 1_000_000.0),
+        (Size::new(999, Multiple::Byte), Multiple::Kilobyte, 0.0),
+        (Size::new(2.5, Multiple::Mebibyte), Multiple::Mebibyte, 2_097_152.0),
+        (Size::new(2.5, Multiple::Mebibyte), Multiple::Kibibyte, 2_621_440.0),
+        (Size::new(1.5, Multiple::Byte), Multiple::Byte, 1.0),
+        (Size::new(3, Multiple::Gigabyte), Multiple::Gigabyte, 3_000_000_000.0),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.coarsen(test.1);
+        assert_eq!(got.into_bytes(), test.2, "input: {:?}, {:?}", size, test.1);
+        assert_eq!(got.multiple(), test.1, "input: {:?}, {:?}", size, test.1);
+        assert!(got <= size, "input: {:?}, {:?}", size, test.1);
+        let unit = Size::new(1, test.1).unwrap().into_bytes();
+        assert_eq!(got.into_bytes() % unit, 0.0, "input: {:?}, {:?}", size, test.1);
+    }
+}
+
+#[test]
+fn multiple_family_byte() {
+    // `Byte` is considered a decimal multiple.
+    assert!(Multiple::Byte.is_decimal());
+    assert!(!Multiple::Byte.is_binary());
+    assert_eq!(Multiple::Byte.base(), 1000);
+    assert_eq!(Multiple::Byte.next_larger(), Some(Multiple::Kilobyte));
+    assert_eq!(Multiple::Byte.next_smaller(), None);
+    assert_eq!(Multiple::Kilobyte.next_smaller(), Some(Multiple::Byte));
+    assert_eq!(Multiple::Kibibyte.next_smaller(), Some(Multiple::Byte));
+
+    let tests = vec![
+        (Multiple::Kilobyte, true, 1000),
+        (Multiple::Megabyte, true, 1000),
+        (Multiple::Gigabyte, true, 1000),
+        (Multiple::Terabyte, true, 1000),
+        (Multiple::Petabyte, true, 1000),
+        (Multiple::Kibibyte, false, 1024),
+        (Multiple::Mebibyte, false, 1024),
+        (Multiple::Gigibyte, false, 1024),
+        (Multiple::Tebibyte, false, 1024),
+        (Multiple::Pebibyte, false, 1024),
+    ];
+
+    for test in tests {
+        assert_eq!(test.0.is_decimal(), test.1, "multiple: {:?}", test.0);
+        assert_eq!(test.0.is_binary(), !test.1, "multiple: {:?}", test.0);
+        assert_eq!(test.0.base(), test.2, "multiple: {:?}", test.0);
+    }
+}
+
+#[test]
+fn navigating_multiples() {
+    let chains = vec![
+        vec![Multiple::Byte, Multiple::Kilobyte, Multiple::Megabyte, Multiple::Gigabyte, Multiple::Terabyte, Multiple::Petabyte],
+        vec![Multiple::Byte, Multiple::Kibibyte, Multiple::Mebibyte, Multiple::Gigibyte, Multiple::Tebibyte, Multiple::Pebibyte],
+    ];
+
+    for chain in chains {
+        for pair in chain.windows(2) {
+            assert_eq!(pair[1].next_smaller(), Some(pair[0]), "multiples: {:?}", pair);
+            // Byte is considered decimal.
+            if pair[0] != Multiple::Byte || pair[1].is_decimal() {
+                assert_eq!(pair[0].next_larger(), Some(pair[1]), "multiples: {:?}", pair);
+            }
+        }
+        assert_eq!(chain.last().unwrap().next_larger(), None, "chain: {:?}", chain);
+    }
+
+    assert_eq!(Multiple::Byte.next_smaller(), None);
+}
+
+#[test]
+fn all_multiples() {
+    let multiples = Multiple::all();
+    assert_eq!(multiples.len(), 11);
+    assert_eq!(multiples.first(), Some(&Multiple::Byte));
+    assert_eq!(multiples.last(), Some(&Multiple::Pebibyte));
+    for pair in multiples.windows(2) {
+        assert!(u128::from(pair[0]) < u128::from(pair[1]), "multiples: {:?}", pair);
+    }
+    for multiple in multiples {
+        let parsed: Multiple = multiple.to_string().parse().unwrap();
+        assert_eq!(parsed, *multiple);
+    }
+}
+
+#[test]
+fn multiple_base_and_exponent() {
+    let tests = vec![
+        (Multiple::Byte, 1000, 0),
+        (Multiple::Kilobyte, 1000, 1),
+        (Multiple::Megabyte, 1000, 2),
+        (Multiple::Petabyte, 1000, 5),
+        (Multiple::Kibibyte, 1024, 1),
+        (Multiple::Mebibyte, 1024, 2),
+        (Multiple::Pebibyte, 1024, 5),
+    ];
+
+    for test in tests {
+        let multiple = test.0;
+        assert_eq!(multiple.base(), test.1, "multiple: {:?}", multiple);
+        assert_eq!(multiple.exponent(), test.2, "multiple: {:?}", multiple);
+        let bytes = u128::from(multiple.base()).pow(multiple.exponent());
+        assert_eq!(u128::from(multiple), bytes, "multiple: {:?}", multiple);
+    }
+}
+
+#[test]
+fn parsing_with_context() {
+    let tests = vec![
+        ("10 KB", Convention::Decimal, Ok(Size::new(10, Multiple::Kilobyte))),
+        ("10 KB", Convention::Binary, Ok(Size::new(10, Multiple::Kibibyte))),
+        ("10 kB", Convention::Binary, Ok(Size::new(10, Multiple::Kilobyte))),
+        ("10 KiB", Convention::Decimal, Ok(Size::new(10, Multiple::Kibibyte))),
+        ("10 MB", Convention::Binary, Ok(Size::new(10, Multiple::Megabyte))),
+        ("10 XB", Convention::Decimal, Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_with_context(test.0, test.1);
+        let want = match test.2 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}, {:?}", test.0, test.1);
+    }
+
+    let decimal = Size::parse_with_context("10 KB", Convention::Decimal).unwrap();
+    let binary = Size::parse_with_context("10 KB", Convention::Binary).unwrap();
+    assert_eq!(decimal.into_bytes(), 10_000.0);
+    assert_eq!(binary.into_bytes(), 10_240.0);
+}
+
+#[test]
+fn humanizing_small_sizes() {
+    for bytes in 0..1000 {
+        let size = Size::new(bytes, Multiple::Byte).unwrap();
+        let want = format!("{} B", bytes);
+        assert_eq!(size.humanize().to_string(), want, "bytes: {}", bytes);
+        assert_eq!(size.humanize().format(&FormatOptions::new()), want, "bytes: {}", bytes);
+        let size = size.with_convention(Convention::Binary);
+        assert_eq!(size.humanize().to_string(), want, "bytes: {}", bytes);
+
+        let size = Size::new(f64::from(bytes) / 1000.0, Multiple::Kilobyte).unwrap();
+        assert_eq!(size.humanize().multiple(), Multiple::Byte, "bytes: {}", bytes);
+    }
+}
+
+#[test]
+fn adding_sizes() {
+    let tests = vec![
+        (Size::new(500, Multiple::Megabyte), Size::new(600, Multiple::Megabyte), Size::new(1100, Multiple::Megabyte)),
+        (Size::new(1, Multiple::Gigabyte), Size::new(500, Multiple::Megabyte), Size::new(1.5, Multiple::Gigabyte)),
+        (Size::new(1, Multiple::Mebibyte), Size::new(512, Multiple::Kibibyte), Size::new(1.5, Multiple::Mebibyte)),
+        (Size::new(1, Multiple::Kilobyte), Size::new(24, Multiple::Byte), Size::new(1.024, Multiple::Kilobyte)),
+        (Size::new(0, Multiple::Byte), Size::new(0, Multiple::Petabyte), Size::new(0, Multiple::Byte)),
+    ];
+
+    for test in tests {
+        let (a, b, want) = (test.0.unwrap(), test.1.unwrap(), test.2.unwrap());
+        let got = a + b;
+        assert_eq!(got, want, "input: {:?} + {:?}", a, b);
+        assert_eq!(got.multiple(), a.multiple(), "input: {:?} + {:?}", a, b);
+        assert_eq!(a.checked_add(b), Some(want), "input: {:?} + {:?}", a, b);
+    }
+
+    let sum = Size::new(500, Multiple::Megabyte).unwrap() + Size::new(600, Multiple::Megabyte).unwrap();
+    assert_eq!(sum.humanize().to_string(), "1.1 GB");
+
+    let max = Size::new(f64::MAX, Multiple::Byte).unwrap();
+    assert_eq!(max.checked_add(max), None);
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding sizes")]
+fn adding_sizes_overflow() {
+    let max = Size::new(f64::MAX, Multiple::Petabyte).unwrap();
+    let _ = max + max;
+}
+
+#[test]
+fn summing_sizes() {
+    let tests = vec![
+        (vec![Size::new(1, Multiple::Kilobyte), Size::new(500, Multiple::Byte)], "1500 B"),
+        (vec![Size::new(1, Multiple::Kilobyte), Size::new(1000, Multiple::Byte)], "2 kB"),
+        (vec![Size::new(512, Multiple::Kibibyte), Size::new(0.5, Multiple::Mebibyte)], "1 MiB"),
+        (vec![Size::new(1, Multiple::Gigabyte)], "1 GB"),
+        (vec![Size::new(1, Multiple::Kibibyte), Size::new(24, Multiple::Kilobyte)], "25024 B"),
+        (vec![Size::new(1, Multiple::Kibibyte), Size::new(1, Multiple::Byte)], "1025 B"),
+        (vec![Size::new(1, Multiple::Kibibyte), Size::new(0.5, Multiple::Kibibyte)], "1536 B"),
+        (vec![], "0 B"),
+    ];
+
+    for test in tests {
+        let sizes: Vec<Size> = test.0.into_iter().map(Result::unwrap).collect();
+        let got: Size = sizes.iter().copied().sum();
+        assert_eq!(got.to_string(), test.1, "input: {:?}", sizes);
+        let got: Size = sizes.iter().sum();
+        assert_eq!(got.to_string(), test.1, "input: {:?}", sizes);
+    }
+
+    let sizes = [Size::new(1, Multiple::Kilobyte).unwrap(), Size::new(500, Multiple::Byte).unwrap()];
+    let total: Size = sizes.iter().sum();
+    assert_eq!(total.into_bytes(), 1500.0);
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding sizes")]
+fn summing_sizes_overflow() {
+    let max = Size::new(f64::MAX, Multiple::Petabyte).unwrap();
+    let _: Size = vec![max, max].into_iter().sum();
+}
+
+#[test]
+fn collecting_sizes() {
+    let tests = vec![
+        vec![Size::new(1, Multiple::Kilobyte), Size::new(500, Multiple::Byte)],
+        vec![Size::new(512, Multiple::Kibibyte), Size::new(0.5, Multiple::Mebibyte)],
+        vec![Size::new(1, Multiple::Kibibyte), Size::new(24, Multiple::Kilobyte)],
+        vec![Size::new(1.5, Multiple::Gigabyte)],
+        vec![],
+    ];
+
+    for test in tests {
+        let sizes: Vec<Size> = test.into_iter().map(Result::unwrap).collect();
+        let sum: Size = sizes.iter().sum();
+        let got: Size = sizes.clone().into_iter().collect();
+        assert_eq!(got, sum, "input: {:?}", sizes);
+        assert_eq!(got.multiple(), sum.multiple(), "input: {:?}", sizes);
+    }
+
+    let sizes = vec![Size::new(512, Multiple::Kibibyte).unwrap(), Size::new(0.5, Multiple::Mebibyte).unwrap()];
+    let total: Size = sizes.into_iter().collect();
+    assert_eq!(total.to_string(), "1 MiB");
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding sizes")]
+fn collecting_sizes_overflow() {
+    let max = Size::new(f64::MAX, Multiple::Petabyte).unwrap();
+    let _: Size = vec![max, max].into_iter().collect();
+}
+
+#[test]
+fn add_assigning_sizes() {
+    let sizes = [
+        Size::new(1, Multiple::Kilobyte).unwrap(),
+        Size::new(500, Multiple::Byte).unwrap(),
+        Size::new(1.5, Multiple::Megabyte).unwrap(),
+        Size::new(1, Multiple::Kibibyte).unwrap(),
+    ];
+
+    let mut total = Size::ZERO;
+    for size in sizes.iter() {
+        total += *size;
+    }
+    let sum: Size = sizes.iter().sum();
+    assert_eq!(total, sum);
+    assert_eq!(total.into_bytes(), 1_502_524.0);
+    // Keeps the multiple of the left-hand side.
+    assert_eq!(total.multiple(), Multiple::Byte);
+
+    let mut total = Size::new(1, Multiple::Gigabyte).unwrap();
+    total += Size::new(500, Multiple::Megabyte).unwrap();
+    assert_eq!(total.to_string(), "1.5 GB");
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding sizes")]
+fn add_assigning_sizes_overflow() {
+    let mut total = Size::new(f64::MAX, Multiple::Petabyte).unwrap();
+    total += total;
+}
+
+#[test]
+fn sub_assigning_sizes() {
+    let mut remaining = Size::new(1, Multiple::Gigabyte).unwrap();
+    remaining -= Size::new(250, Multiple::Megabyte).unwrap();
+    assert_eq!(remaining.to_string(), "0.75 GB");
+    remaining -= Size::new(1, Multiple::Gigabyte).unwrap();
+    assert_eq!(remaining.to_string(), "0 GB");
+    assert_eq!(remaining, Size::ZERO);
+}
+
+#[test]
+fn absolute_difference_of_sizes() {
+    let tests = vec![
+        (Size::new(1, Multiple::Megabyte), Size::new(1, Multiple::Kilobyte), "999 kB"),
+        (Size::new(1, Multiple::Gigabyte), Size::new(1, Multiple::Gigabyte), "0 B"),
+        (Size::new(1, Multiple::Gigibyte), Size::new(512, Multiple::Mebibyte), "512 MiB"),
+        (Size::new(1, Multiple::Kibibyte), Size::new(1, Multiple::Kilobyte), "24 B"),
+        (Size::new(1.5, Multiple::Byte), Size::new(1, Multiple::Byte), "0.5 B"),
+    ];
+
+    for test in tests {
+        let (a, b) = (test.0.unwrap(), test.1.unwrap());
+        assert_eq!(a.abs_diff(&b).to_string(), test.2, "input: {:?}, {:?}", a, b);
+        assert_eq!(b.abs_diff(&a).to_string(), test.2, "input: {:?}, {:?}", b, a);
+    }
+
+    let a = Size::new(1, Multiple::Megabyte).unwrap();
+    let b = Size::new(1, Multiple::Kilobyte).unwrap();
+    assert_eq!(a.abs_diff(&b), Size::new(999_000, Multiple::Byte).unwrap());
+    assert_eq!(b.abs_diff(&a).into_bytes(), 999_000.0);
+
+    // Overflows when converted into bytes.
+    let max = Size::new(f64::MAX, Multiple::Pebibyte).unwrap();
+    let got = max.abs_diff(&Size::new(1, Multiple::Byte).unwrap());
+    assert_eq!(got.value(), f64::MAX);
+    assert_eq!(got.multiple(), Multiple::Pebibyte);
+}
+
+#[test]
+fn scaling_size() {
+    let tests = vec![
+        (Size::new(1, Multiple::Kilobyte), 1.5, "1500 B"),
+        (Size::new(1, Multiple::Kilobyte), 2.0, "2 kB"),
+        (Size::new(10, Multiple::Gigabyte), 1.1, "11 GB"),
+        (Size::new(1, Multiple::Mebibyte), 0.5, "512 KiB"),
+        (Size::new(1, Multiple::Kibibyte), 1.5, "1536 B"),
+        (Size::new(3, Multiple::Byte), 0.5, "2 B"),
+        (Size::new(1, Multiple::Byte), 0.4, "0 B"),
+        (Size::new(5, Multiple::Terabyte), 0.0, "0 B"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.scale(test.1).to_string(), test.2, "input: {:?}, {}", size, test.1);
+    }
+
+    let size = Size::new(1, Multiple::Kilobyte).unwrap().scale(1.5);
+    assert_eq!(size.into_bytes(), 1500.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid factor to scale size")]
+fn scaling_size_negative_factor() {
+    let _ = Size::new(1, Multiple::Kilobyte).unwrap().scale(-1.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid factor to scale size")]
+fn scaling_size_nan_factor() {
+    let _ = Size::new(1, Multiple::Kilobyte).unwrap().scale(f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "overflow when scaling size")]
+fn scaling_size_overflow() {
+    let _ = Size::new(1, Multiple::Kilobyte).unwrap().scale(f64::INFINITY);
+}
+
+#[test]
+fn ratio_of_sizes() {
+    let tests = vec![
+        (Size::new(250, Multiple::Megabyte), Size::new(1, Multiple::Gigabyte), 0.25),
+        (Size::new(1, Multiple::Gigabyte), Size::new(250, Multiple::Megabyte), 4.0),
+        (Size::new(512, Multiple::Kibibyte), Size::new(1, Multiple::Mebibyte), 0.5),
+        (Size::new(1, Multiple::Kibibyte), Size::new(1, Multiple::Kilobyte), 1.024),
+        (Size::new(0, Multiple::Byte), Size::new(1, Multiple::Petabyte), 0.0),
+        (Size::new(f64::MAX, Multiple::Pebibyte), Size::new(f64::MAX / 2.0, Multiple::Pebibyte), 2.0),
+        (Size::new(1, Multiple::Byte), Size::new(0, Multiple::Byte), f64::INFINITY),
+        (Size::new(1, Multiple::Petabyte), Size::new(0, Multiple::Kilobyte), f64::INFINITY),
+    ];
+
+    for test in tests {
+        let (a, b) = (test.0.unwrap(), test.1.unwrap());
+        assert_eq!(a.ratio(&b), test.2, "input: {:?}, {:?}", a, b);
+    }
+
+    let zero = Size::new(0, Multiple::Byte).unwrap();
+    assert!(zero.ratio(&zero).is_nan());
+}
+
+#[test]
+fn subtracting_sizes() {
+    let tests = vec![
+        (Size::new(1100, Multiple::Megabyte), Size::new(600, Multiple::Megabyte), Size::new(500, Multiple::Megabyte), true),
+        (Size::new(1, Multiple::Gigabyte), Size::new(500, Multiple::Megabyte), Size::new(0.5, Multiple::Gigabyte), true),
+        (Size::new(1, Multiple::Mebibyte), Size::new(1, Multiple::Mebibyte), Size::new(0, Multiple::Mebibyte), true),
+        // Saturates at zero.
+        (Size::new(1, Multiple::Kilobyte), Size::new(1, Multiple::Kibibyte), Size::new(0, Multiple::Kilobyte), false),
+        (Size::new(10, Multiple::Byte), Size::new(1, Multiple::Petabyte), Size::new(0, Multiple::Byte), false),
+    ];
+
+    for test in tests {
+        let (a, b, want) = (test.0.unwrap(), test.1.unwrap(), test.2.unwrap());
+        let got = a - b;
+        assert_eq!(got, want, "input: {:?} - {:?}", a, b);
+        assert_eq!(got.multiple(), a.multiple(), "input: {:?} - {:?}", a, b);
+        assert_eq!(a.checked_sub(b).is_some(), test.3, "input: {:?} - {:?}", a, b);
+    }
+}
+
+#[test]
+fn multiplying_and_dividing_sizes() {
+    let size = Size::new(2, Multiple::Gigabyte).unwrap();
+    let got = size * 3;
+    assert_eq!(got, Size::new(6, Multiple::Gigabyte).unwrap());
+    assert_eq!(got.multiple(), Multiple::Gigabyte);
+    assert_eq!(size.checked_mul(0), Some(Size::new(0, Multiple::Byte).unwrap()));
+
+    let size = Size::new(3, Multiple::Mebibyte).unwrap();
+    let got = size / 2;
+    assert_eq!(got, Size::new(1.5, Multiple::Mebibyte).unwrap());
+    assert_eq!(got.multiple(), Multiple::Mebibyte);
+    assert_eq!(size.checked_div(3), Some(Size::new(1, Multiple::Mebibyte).unwrap()));
+    assert_eq!(size.checked_div(0), None);
+
+    let max = Size::new(f64::MAX, Multiple::Byte).unwrap();
+    assert_eq!(max.checked_mul(2), None);
+    assert_eq!(max.checked_mul(1), Some(max));
+}
+
+#[test]
+#[should_panic(expected = "attempt to divide size by zero")]
+fn dividing_size_by_zero() {
+    let _ = Size::new(1, Multiple::Byte).unwrap() / 0;
+}
+
+#[test]
+fn checked_size_from_parts() {
+    let tests = vec![
+        (0, Multiple::Pebibyte, Some(0.0)),
+        (10, Multiple::Pebibyte, Some(11_258_999_068_426_240.0)),
+        (u128::MAX / 2, Multiple::Byte, Some(2f64.powi(127))),
+        (1 << 77, Multiple::Pebibyte, Some(2f64.powi(127))),
+        (u128::MAX / 1024u128.pow(5) + 1, Multiple::Pebibyte, None),
+        (u128::MAX, Multiple::Kilobyte, None),
+        // Fits in an `u128`, but not once rounded into a `f64`.
+        (u128::MAX, Multiple::Byte, None),
+        (u128::MAX / 1024u128.pow(5), Multiple::Pebibyte, None),
+    ];
+
+    for test in tests {
+        let got = Size::checked_from_parts(test.0, test.1);
+        assert_eq!(got.map(|size| size.into_bytes()), test.2, "input: {}, {:?}", test.0, test.1);
+        if let Some(size) = got {
+            assert_eq!(size.multiple(), test.1, "input: {}, {:?}", test.0, test.1);
+            assert!(size.to_integer::<u128>().is_ok(), "input: {}, {:?}", test.0, test.1);
+        }
+    }
+}
+
+#[test]
+fn converting_multiple_into_u128() {
+    let tests = vec![
+        (Multiple::Byte, 1),
+        (Multiple::Kilobyte, 1000),
+        (Multiple::Megabyte, 1000u128.pow(2)),
+        (Multiple::Gigabyte, 1000u128.pow(3)),
+        (Multiple::Terabyte, 1000u128.pow(4)),
+        (Multiple::Petabyte, 1000u128.pow(5)),
+        (Multiple::Kibibyte, 1024),
+        (Multiple::Mebibyte, 1024u128.pow(2)),
+        (Multiple::Gigibyte, 1024u128.pow(3)),
+        (Multiple::Tebibyte, 1024u128.pow(4)),
+        (Multiple::Pebibyte, 1024u128.pow(5)),
+    ];
+
+    for test in tests {
+        assert_eq!(u128::from(test.0), test.1, "multiple: {:?}", test.0);
+        let bytes: u128 = test.0.into();
+        assert_eq!(bytes, Size::new(1, test.0).unwrap().as_bytes(), "multiple: {:?}", test.0);
+    }
+}
+
+#[test]
+fn converting_into_f64() {
+    let tests = vec![
+        (Multiple::Byte, 1.0),
+        (Multiple::Kilobyte, 1e3),
+        (Multiple::Gigibyte, 1_073_741_824.0),
+        (Multiple::Petabyte, 1e15),
+        (Multiple::Pebibyte, 1.125899906842624e15),
+    ];
+
+    for test in tests {
+        assert_eq!(f64::from(test.0), test.1, "multiple: {:?}", test.0);
+        let bytes: f64 = Size::new(1, test.0).unwrap().into();
+        assert_eq!(bytes, test.1, "multiple: {:?}", test.0);
+    }
+
+    let bytes: f64 = Size::new(1.5, Multiple::Kibibyte).unwrap().into();
+    assert_eq!(bytes, 1536.0);
+    let bytes: f64 = Size::new(f64::MAX, Multiple::Pebibyte).unwrap().into();
+    assert_eq!(bytes, f64::INFINITY);
+}
+
+#[test]
+fn formatting_nice_string() {
+    let tests = vec![
+        (Size::new(1536, Multiple::Mebibyte), Convention::Binary, "1.50 GiB"),
+        (Size::new(15360, Multiple::Mebibyte), Convention::Binary, "15.0 GiB"),
+        (Size::new(153600, Multiple::Mebibyte), Convention::Binary, "150 GiB"),
+        (Size::new(1023, Multiple::Kibibyte), Convention::Binary, "1023 KiB"),
+        (Size::new(1, Multiple::Mebibyte), Convention::Decimal, "1.05 MB"),
+        (Size::new(0, Multiple::Byte), Convention::Decimal, "0 B"),
+        (Size::new(3, Multiple::Byte), Convention::Binary, "3 B"),
+        (Size::new(7, Multiple::Byte), Convention::Decimal, "7 B"),
+        (Size::new(42, Multiple::Byte), Convention::Decimal, "42 B"),
+        (Size::new(999, Multiple::Byte), Convention::Decimal, "999 B"),
+        (Size::new(1023, Multiple::Byte), Convention::Binary, "1023 B"),
+        (Size::new(12_345, Multiple::Byte), Convention::Decimal, "12.3 kB"),
+        (Size::new(123_456_789, Multiple::Byte), Convention::Decimal, "123 MB"),
+        (Size::new(9.999, Multiple::Gigabyte), Convention::Decimal, "10.0 GB"),
+        (Size::new(99.99, Multiple::Gigabyte), Convention::Decimal, "100 GB"),
+        (Size::new(5000, Multiple::Petabyte), Convention::Decimal, "5000 PB"),
+        // Rounding up to the next multiple.
+        (Size::new(999.6, Multiple::Kilobyte), Convention::Decimal, "1.00 MB"),
+        (Size::new(999.5, Multiple::Kilobyte), Convention::Decimal, "1.00 MB"),
+        (Size::new(999.4, Multiple::Kilobyte), Convention::Decimal, "999 kB"),
+        (Size::new(999.6, Multiple::Byte), Convention::Decimal, "1.00 kB"),
+        (Size::new(1023.7, Multiple::Kibibyte), Convention::Binary, "1.00 MiB"),
+        (Size::new(1023.5, Multiple::Kibibyte), Convention::Binary, "1.00 MiB"),
+        (Size::new(1023.4, Multiple::Kibibyte), Convention::Binary, "1023 KiB"),
+        (Size::new(1023.6, Multiple::Byte), Convention::Binary, "1.00 KiB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_nice_string(test.1), test.2, "input: {:?}, {:?}", size, test.1);
+    }
+}
+
+#[test]
+fn multiple_u8_representation() {
+    let tests = vec![
+        (Multiple::Byte, 0),
+        (Multiple::Kilobyte, 1),
+        (Multiple::Megabyte, 2),
+        (Multiple::Gigabyte, 3),
+        (Multiple::Terabyte, 4),
+        (Multiple::Petabyte, 5),
+        (Multiple::Kibibyte, 17),
+        (Multiple::Mebibyte, 18),
+        (Multiple::Gigibyte, 19),
+        (Multiple::Tebibyte, 20),
+        (Multiple::Pebibyte, 21),
+    ];
+
+    for test in tests {
+        assert_eq!(test.0.as_u8(), test.1, "multiple: {:?}", test.0);
+        assert_eq!(Multiple::from_u8(test.1), Some(test.0), "multiple: {:?}", test.0);
+    }
+
+    for value in [6, 16, 22, 255] {
+        assert_eq!(Multiple::from_u8(value), None, "value: {}", value);
+    }
+}
+
+#[test]
+fn displaying_alternate_humanized() {
+    // Prefers binary multiples.
+    let tests = vec![
+        (Size::new(1_048_576, Multiple::Byte), "1 MiB"),
+        (Size::new(1500, Multiple::Kilobyte), "1.430511 MiB"),
+        (Size::new(1000, Multiple::Kilobyte), "976.5625 KiB"),
+        (Size::new(999, Multiple::Byte), "999 B"),
+        (Size::new(1000, Multiple::Byte), "1000 B"),
+        (Size::new(1024, Multiple::Byte), "1 KiB"),
+        (Size::new(2048, Multiple::Kibibyte), "2 MiB"),
+        (Size::new(1023, Multiple::Kibibyte), "1023 KiB"),
+        (Size::new(1, Multiple::Gigabyte), "953.674316 MiB"),
+        (Size::new(1.5, Multiple::Gigibyte), "1.5 GiB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(format!("{:#}", size), test.1, "input: {:?}", size);
+        // The default format is never scaled.
+        assert_eq!(size.to_string(), format!("{} {}", size.value(), size.multiple()), "input: {:?}", size);
+    }
+
+    // Unless a convention is set.
+    let tests = vec![
+        (Size::new(1_048_576, Multiple::Byte), Convention::Decimal, "1.048576 MB"),
+        (Size::new(1500, Multiple::Kilobyte), Convention::Decimal, "1.5 MB"),
+        (Size::new(1000, Multiple::Kilobyte), Convention::Decimal, "1 MB"),
+        (Size::new(1000, Multiple::Byte), Convention::Decimal, "1 kB"),
+        (Size::new(999.5, Multiple::Kilobyte), Convention::Decimal, "999.5 kB"),
+        (Size::new(2048, Multiple::Kibibyte), Convention::Decimal, "2.097152 MB"),
+        (Size::new(1, Multiple::Gigabyte), Convention::Decimal, "1 GB"),
+        (Size::new(1.0 / 3.0, Multiple::Megabyte), Convention::Decimal, "333.333333 kB"),
+        (Size::new(1, Multiple::Gigabyte), Convention::Binary, "953.674316 MiB"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap().with_convention(test.1);
+        assert_eq!(format!("{:#}", size), test.2, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn finding_smallest_container() {
+    let floppy = Size::new(1.44, Multiple::Megabyte).unwrap();
+    let cd = Size::new(700, Multiple::Megabyte).unwrap();
+    let dvd = Size::new(4.7, Multiple::Gigabyte).unwrap();
+    let blu_ray = Size::new(25, Multiple::Gigabyte).unwrap();
+    let media = [blu_ray, cd, dvd, floppy];
+
+    let tests = vec![
+        (Size::new(0, Multiple::Byte), Some(floppy)),
+        (Size::new(1, Multiple::Megabyte), Some(floppy)),
+        (Size::new(700, Multiple::Megabyte), Some(cd)),
+        // Fits a DVD, but not a CD.
+        (Size::new(1, Multiple::Gigabyte), Some(dvd)),
+        (Size::new(4.7, Multiple::Gigabyte), Some(dvd)),
+        (Size::new(10, Multiple::Gigibyte), Some(blu_ray)),
+        (Size::new(1, Multiple::Terabyte), None),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.smallest_container(&media);
+        assert_eq!(got, test.1, "input: {:?}", size);
+    }
+
+    let size = Size::new(1, Multiple::Byte).unwrap();
+    assert_eq!(size.smallest_container(&[]), None);
+}
+
+#[test]
+fn displaying_with_formatting_options() {
+    let size = Size::new(1, Multiple::Byte).unwrap();
+    assert_eq!(format!("{:.3}", size), "1.000 B");
+    assert_eq!(format!("{:.0}", Size::new(1.5, Multiple::Kibibyte).unwrap()), "2 KiB");
+    assert_eq!(format!("{:>12}", size), "         1 B");
+    assert_eq!(format!("{:<8}|", size), "1 B     |");
+    assert_eq!(format!("{:*^9}", size), "***1 B***");
+    assert_eq!(format!("{:>10.2}", size), "    1.00 B");
+    assert_eq!(format!("{:2}", Size::new(10, Multiple::Megabyte).unwrap()), "10 MB");
+
+    let size = Size::new(1500, Multiple::Kilobyte).unwrap().with_convention(Convention::Decimal);
+    assert_eq!(format!("{:#.3}", size), "1.500 MB");
+    assert_eq!(format!("{:>#8}", size), "  1.5 MB");
+    let size = Size::new(1, Multiple::Mebibyte).unwrap();
+    assert_eq!(format!("{:>#8}", size), "   1 MiB");
+}
+
+#[test]
+fn writing_size_into_buffer() {
+    use std::fmt;
+
+    /// Writer into a fixed size buffer.
+    struct Buffer {
+        data: [u8; 16],
+        len: usize,
+    }
+
+    impl fmt::Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            if end > self.data.len() {
+                return Err(fmt::Error);
+            }
+            self.data[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let tests = vec![
+        Size::new(10, Multiple::Megabyte).unwrap(),
+        Size::new(1.5, Multiple::Kibibyte).unwrap(),
+        Size::new(0, Multiple::Byte).unwrap(),
+    ];
+
+    for size in tests {
+        let mut buffer = Buffer { data: [0; 16], len: 0 };
+        size.write_to(&mut buffer).unwrap();
+        let got = std::str::from_utf8(&buffer.data[..buffer.len]).unwrap();
+        assert_eq!(got, size.to_string(), "input: {:?}", size);
+    }
+
+    let size = Size::new(1.0 / 3.0, Multiple::Pebibyte).unwrap();
+    let mut buffer = Buffer { data: [0; 16], len: 0 };
+    assert!(size.write_to(&mut buffer).is_err());
+}
+
+#[test]
+fn comparing_huge_sizes() {
+    use std::cmp::Ordering;
+
+    let max = f64::MAX;
+    let tests = vec![
+        // Both overflow when converted into bytes.
+        (Size::new(max, Multiple::Pebibyte), Size::new(max / 2.0, Multiple::Pebibyte), Ordering::Greater),
+        (Size::new(max / 2.0, Multiple::Pebibyte), Size::new(max, Multiple::Pebibyte), Ordering::Less),
+        (Size::new(max, Multiple::Pebibyte), Size::new(max, Multiple::Pebibyte), Ordering::Equal),
+        (Size::new(max, Multiple::Petabyte), Size::new(max, Multiple::Pebibyte), Ordering::Less),
+        // Only one overflows.
+        (Size::new(max, Multiple::Kilobyte), Size::new(max, Multiple::Byte), Ordering::Greater),
+        (Size::new(1, Multiple::Byte), Size::new(max, Multiple::Pebibyte), Ordering::Less),
+        (Size::new(max, Multiple::Pebibyte), Size::new(1, Multiple::Byte), Ordering::Greater),
+        // Regular sizes.
+        (Size::new(1, Multiple::Kilobyte), Size::new(1000, Multiple::Byte), Ordering::Equal),
+        (Size::new(0, Multiple::Kilobyte), Size::new(-0.0, Multiple::Byte), Ordering::Equal),
+    ];
+
+    for test in tests {
+        let (a, b) = (test.0.unwrap(), test.1.unwrap());
+        assert_eq!(a.partial_cmp(&b), Some(test.2), "input: {:?}, {:?}", a, b);
+        assert_eq!(a == b, test.2 == Ordering::Equal, "input: {:?}, {:?}", a, b);
+    }
+
+    let mut sizes = vec![
+        Size::new(max, Multiple::Pebibyte).unwrap(),
+        Size::new(1, Multiple::Byte).unwrap(),
+        Size::new(max / 2.0, Multiple::Pebibyte).unwrap(),
+    ];
+    sort_sizes(&mut sizes);
+    assert_eq!(sizes, vec![
+        Size::new(1, Multiple::Byte).unwrap(),
+        Size::new(max / 2.0, Multiple::Pebibyte).unwrap(),
+        Size::new(max, Multiple::Pebibyte).unwrap(),
+    ]);
+}
+
+#[test]
+fn ordering_sizes() {
+    use std::cmp::Ordering;
+    use std::collections::BTreeMap;
+
+    let mut sizes = [
+        Size::new(1, Multiple::Mebibyte).unwrap(),
+        Size::new(1, Multiple::Kibibyte).unwrap(),
+        Size::new(1, Multiple::Megabyte).unwrap(),
+        Size::new(1000, Multiple::Byte).unwrap(),
+        Size::new(1, Multiple::Gigibyte).unwrap(),
+        Size::new(1, Multiple::Kilobyte).unwrap(),
+        Size::new(1, Multiple::Gigabyte).unwrap(),
+    ];
+    sizes.sort();
+    let got: Vec<String> = sizes.iter().map(|size| size.to_string()).collect();
+    assert_eq!(got, vec!["1000 B", "1 kB", "1 KiB", "1 MB", "1 MiB", "1 GB", "1 GiB"]);
+
+    let a = Size::new(1000, Multiple::Byte).unwrap();
+    let b = Size::new(1, Multiple::Kilobyte).unwrap();
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert_eq!(a.max(Size::new(1, Multiple::Kibibyte).unwrap()), Size::new(1024, Multiple::Byte).unwrap());
+
+    let mut map = BTreeMap::new();
+    map.insert(a, "a");
+    map.insert(b, "b");
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(&Size::new(0.001, Multiple::Megabyte).unwrap()), Some(&"b"));
+}
+
+#[test]
+fn mapping_size_multiple() {
+    let tests = vec![
+        (Size::new(10, Multiple::Kilobyte), Multiple::Megabyte),
+        (Size::new(1.5, Multiple::Kibibyte), Multiple::Mebibyte),
+        (Size::new(3, Multiple::Byte), Multiple::Kilobyte),
+        (Size::new(2, Multiple::Pebibyte), Multiple::Pebibyte),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let got = size.map_multiple(|multiple| multiple.next_larger().unwrap_or(multiple));
+        assert_eq!(got.multiple(), test.1, "input: {:?}", size);
+        assert_eq!(got.to_string().split(' ').next(), size.to_string().split(' ').next(), "input: {:?}", size);
+    }
+}
+
+#[test]
+fn hashing_sizes() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    set.insert(Size::new(1000, Multiple::Byte).unwrap());
+    set.insert(Size::new(1, Multiple::Kilobyte).unwrap());
+    set.insert(Size::new(0.001, Multiple::Megabyte).unwrap());
+    assert_eq!(set.len(), 1);
+
+    set.insert(Size::new(1, Multiple::Kibibyte).unwrap());
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&Size::new(1024, Multiple::Byte).unwrap()));
+
+    let mut set = HashSet::new();
+    set.insert(Size::new(0, Multiple::Byte).unwrap());
+    set.insert(Size::new(-0.0, Multiple::Gigabyte).unwrap());
+    assert_eq!(set.len(), 1);
+
+    let mut set = HashSet::new();
+    set.insert(Size::new(f64::MAX, Multiple::Pebibyte).unwrap());
+    set.insert(Size::new(f64::MAX, Multiple::Pebibyte).unwrap());
+    set.insert(Size::new(f64::MAX / 2.0, Multiple::Pebibyte).unwrap());
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn formatting_with_aliases() {
+    let aliases = [(Multiple::Kilobyte, "KC"), (Multiple::Kibibyte, "Kbyte")];
+    let tests = vec![
+        (Size::new(10, Multiple::Kilobyte), "10 KC"),
+        (Size::new(1.5, Multiple::Kibibyte), "1.5 Kbyte"),
+        (Size::new(10, Multiple::Megabyte), "10 MB"),
+        (Size::new(0, Multiple::Byte), "0 B"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.format_with_aliases(&aliases), test.1, "input: {:?}", size);
+        assert_eq!(size.format_with_aliases(&[]), size.to_string(), "input: {:?}", size);
+    }
+}
+
+#[test]
+fn size_is_eq() {
+    fn all_equal<T: Eq>(values: &[T]) -> bool {
+        values.windows(2).all(|pair| pair[0] == pair[1])
+    }
+
+    assert_eq!(Size::new(1, Multiple::Kilobyte).unwrap(), Size::new(1000, Multiple::Byte).unwrap());
+    assert!(all_equal(&[
+        Size::new(1, Multiple::Kilobyte).unwrap(),
+        Size::new(1000, Multiple::Byte).unwrap(),
+        Size::new(0.001, Multiple::Megabyte).unwrap(),
+    ]));
+    assert!(!all_equal(&[
+        Size::new(1, Multiple::Kilobyte).unwrap(),
+        Size::new(1, Multiple::Kibibyte).unwrap(),
+    ]));
+
+    let size = Size::new(f64::MAX, Multiple::Pebibyte).unwrap();
+    assert_eq!(size, size);
+}
+
+#[test]
+#[allow(deprecated)]
+fn saturating_bytes_u64() {
+    let tests = vec![
+        (Size::new(10, Multiple::Megabyte), 10_000_000),
+        (Size::new(1.5, Multiple::Kibibyte), 1536),
+        (Size::new(2.5, Multiple::Byte), 2),
+        (Size::new(0, Multiple::Byte), 0),
+        (Size::new(-1, Multiple::Kilobyte), 0),
+        (Size::new(u64::MAX as f64, Multiple::Byte), u64::MAX),
+        (Size::new(16, Multiple::Pebibyte), 18_014_398_509_481_984),
+        (Size::new(1e10, Multiple::Pebibyte), u64::MAX),
+        (Size::new(f64::MAX, Multiple::Pebibyte), u64::MAX),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.bytes_saturating_u64(), test.1, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn checked_and_saturating_bytes() {
+    let tests = vec![
+        (Size::new(1, Multiple::Kilobyte), Some(1000), Some(1000), 1000),
+        (Size::new(2.5, Multiple::Byte), Some(2), Some(2), 2),
+        (Size::new(0, Multiple::Byte), Some(0), Some(0), 0),
+        (Size::new(-1, Multiple::Kilobyte), None, None, 0),
+        (Size::new(4_294_967_295u32, Multiple::Byte), Some(4_294_967_295), Some(4_294_967_295), u32::MAX),
+        (Size::new(4, Multiple::Gigibyte), Some(4_294_967_296), None, u32::MAX),
+        (Size::new(1, Multiple::Pebibyte), Some(1_125_899_906_842_624), None, u32::MAX),
+        (Size::new(1e10, Multiple::Pebibyte), None, None, u32::MAX),
+        (Size::new(f64::MAX, Multiple::Pebibyte), None, None, u32::MAX),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_bytes_checked_u64(), test.1, "input: {:?}", size);
+        assert_eq!(size.to_bytes_checked_u32(), test.2, "input: {:?}", size);
+        assert_eq!(size.to_bytes_saturating_u32(), test.3, "input: {:?}", size);
+        let saturated = test.1.unwrap_or(if size.into_bytes() < 0.0 { 0 } else { u64::MAX });
+        assert_eq!(size.to_bytes_saturating_u64(), saturated, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn rounding_size_to_bytes() {
+    let tests = vec![
+        (Size::new(1.5, Multiple::Byte), [1, 2, 2, 1]),
+        (Size::new(1.4, Multiple::Byte), [1, 2, 1, 1]),
+        (Size::new(1.6, Multiple::Byte), [1, 2, 2, 1]),
+        (Size::new(2, Multiple::Byte), [2, 2, 2, 2]),
+        (Size::new(0.0001, Multiple::Kilobyte), [0, 1, 0, 0]),
+        (Size::new(1.5, Multiple::Kibibyte), [1536, 1536, 1536, 1536]),
+        (Size::new(0, Multiple::Byte), [0, 0, 0, 0]),
+        (Size::new(-1.5, Multiple::Byte), [0, 0, 0, 0]),
+        (Size::new(f64::MAX, Multiple::Pebibyte), [u128::MAX, u128::MAX, u128::MAX, u128::MAX]),
+    ];
+
+    let modes = [Rounding::Floor, Rounding::Ceil, Rounding::Nearest, Rounding::Truncate];
+    for test in tests {
+        let size = test.0.unwrap();
+        for (mode, want) in modes.iter().zip(test.1.iter()) {
+            assert_eq!(size.to_bytes_rounded(*mode), *want, "input: {:?}, {:?}", size, mode);
+        }
+        // The integer conversions truncate.
+        if let Ok(bytes) = size.to_integer::<u128>() {
+            assert_eq!(bytes, size.to_bytes_rounded(Rounding::Truncate), "input: {:?}", size);
+        }
+    }
+}
+
+#[test]
+fn size_as_bytes() {
+    let tests = vec![
+        (Size::new(1, Multiple::Pebibyte), 1024u128.pow(5)),
+        (Size::new(1, Multiple::Petabyte), 1000u128.pow(5)),
+        (Size::new(1.5, Multiple::Kibibyte), 1536),
+        (Size::new(2.5, Multiple::Byte), 2),
+        (Size::new(0, Multiple::Byte), 0),
+        (Size::new(-1, Multiple::Kilobyte), 0),
+        (Size::new(1e10, Multiple::Pebibyte), 11_258_999_068_426_240_000_000_000),
+        (Size::new(f64::MAX, Multiple::Byte), u128::MAX),
+        (Size::new(f64::MAX, Multiple::Pebibyte), u128::MAX),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.as_bytes(), test.1, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn comparing_size_with_u64() {
+    use std::cmp::Ordering;
+
+    let size = Size::new(1, Multiple::Kilobyte).unwrap();
+    assert!(size == 1000u64);
+    assert!(size > 999);
+    assert!(size < 1001);
+    assert!(size != 1024);
+    assert!(1000u64 == size);
+    assert!(4096u64 > size);
+    assert!(999u64 < size);
+
+    let tests = vec![
+        (Size::new(10, Multiple::Megabyte), 10_000_000, Ordering::Equal),
+        (Size::new(1.5, Multiple::Kibibyte), 1536, Ordering::Equal),
+        (Size::new(2.5, Multiple::Byte), 2, Ordering::Greater),
+        (Size::new(2.5, Multiple::Byte), 3, Ordering::Less),
+        (Size::new(0, Multiple::Byte), 0, Ordering::Equal),
+        (Size::new(-1, Multiple::Kilobyte), 0, Ordering::Less),
+        (Size::new(16, Multiple::Pebibyte), 18_014_398_509_481_984, Ordering::Equal),
+        (Size::new(16, Multiple::Pebibyte), 18_014_398_509_481_985, Ordering::Less),
+        (Size::new(16, Multiple::Pebibyte), u64::MAX, Ordering::Less),
+        // Larger than any `u64`.
+        (Size::new(u64::MAX as f64, Multiple::Byte), u64::MAX, Ordering::Greater),
+        (Size::new(1e10, Multiple::Pebibyte), u64::MAX, Ordering::Greater),
+        (Size::new(f64::MAX, Multiple::Pebibyte), u64::MAX, Ordering::Greater),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.partial_cmp(&test.1), Some(test.2), "input: {:?}, {}", size, test.1);
+        assert_eq!(test.1.partial_cmp(&size), Some(test.2.reverse()), "input: {:?}, {}", size, test.1);
+        assert_eq!(size == test.1, test.2 == Ordering::Equal, "input: {:?}, {}", size, test.1);
+        assert_eq!(test.1 == size, test.2 == Ordering::Equal, "input: {:?}, {}", size, test.1);
+    }
+}
+
+#[test]
+fn size_from_bytes() {
+    let tests = vec![
+        (0, "0 B", "0 B"),
+        (999, "999 B", "999 B"),
+        (1000, "1 kB", "1000 B"),
+        (1024, "1024 B", "1 KiB"),
+        (1536, "1536 B", "1536 B"),
+        (1_500_000, "1500 kB", "1500000 B"),
+        (1_000_000, "1 MB", "1000000 B"),
+        (1_048_576, "1048576 B", "1 MiB"),
+        (1_073_741_824, "1073741824 B", "1 GiB"),
+        (3_000_000_000_000, "3 TB", "2929687500 KiB"),
+        (10u128.pow(18), "1000 PB", "976562500000000 KiB"),
+    ];
+
+    for test in tests {
+        assert_eq!(Size::from_bytes(test.0).to_string(), test.1, "input: {}", test.0);
+        assert_eq!(Size::from_bytes(test.0).into_bytes(), test.0 as f64, "input: {}", test.0);
+        assert_eq!(Size::from_bytes_binary(test.0).to_string(), test.2, "input: {}", test.0);
+        assert_eq!(Size::from_bytes_binary(test.0).into_bytes(), test.0 as f64, "input: {}", test.0);
+    }
+}
+
+#[test]
+fn size_to_words() {
+    let tests = vec![
+        (Size::new(0, Multiple::Byte), "0 bytes"),
+        (Size::new(1, Multiple::Byte), "1 byte"),
+        (Size::new(1.5, Multiple::Byte), "1.5 bytes"),
+        (Size::new(999, Multiple::Byte), "999 bytes"),
+        (Size::new(1, Multiple::Kilobyte), "1 thousand bytes"),
+        (Size::new(1, Multiple::Kibibyte), "1.02 thousand bytes"),
+        (Size::new(2.5, Multiple::Megabyte), "2.5 million bytes"),
+        (Size::new(1.5, Multiple::Gigabyte), "1.5 billion bytes"),
+        (Size::new(1, Multiple::Gigibyte), "1.07 billion bytes"),
+        (Size::new(42, Multiple::Terabyte), "42 trillion bytes"),
+        (Size::new(3, Multiple::Petabyte), "3 quadrillion bytes"),
+        (Size::new(5000, Multiple::Petabyte), "5 quintillion bytes"),
+        (Size::new(1e9, Multiple::Petabyte), "1000000 quintillion bytes"),
+        // Rounding up to the next magnitude.
+        (Size::new(999.999, Multiple::Byte), "1 thousand bytes"),
+        (Size::new(999_994, Multiple::Byte), "999.99 thousand bytes"),
+        (Size::new(999_999, Multiple::Byte), "1 million bytes"),
+        (Size::new(999_999_999, Multiple::Byte), "1 billion bytes"),
+        (Size::new(-999_999, Multiple::Byte), "-1 million bytes"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_words(), test.1, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn size_to_string_long() {
+    let tests = vec![
+        (Size::new(1, Multiple::Byte), "1 byte"),
+        (Size::new(2, Multiple::Byte), "2 bytes"),
+        (Size::new(0, Multiple::Byte), "0 bytes"),
+        (Size::new(1, Multiple::Kilobyte), "1 kilobyte"),
+        (Size::new(1.5, Multiple::Kilobyte), "1.5 kilobytes"),
+        (Size::new(5, Multiple::Megabyte), "5 megabytes"),
+        (Size::new(0.5, Multiple::Petabyte), "0.5 petabytes"),
+        (Size::new(1, Multiple::Mebibyte), "1 mebibyte"),
+        (Size::new(12, Multiple::Mebibyte), "12 mebibytes"),
+        (Size::new(3, Multiple::Gigibyte), "3 gibibytes"),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        assert_eq!(size.to_string_long(), test.1, "input: {:?}", size);
+    }
+}
+
+#[test]
+fn size_value() {
+    assert_eq!("5 MiB".parse::<Size>().unwrap().multiple(), Multiple::Mebibyte);
+
+    let tests = vec![
+        ("5 MiB", 5.0),
+        ("1.5 kB", 1.5),
+        ("0 B", 0.0),
+        ("16 bit", 2.0),
+        ("1024 MiB", 1024.0),
+    ];
+    for test in tests {
+        let size: Size = test.0.parse().unwrap();
+        assert_eq!(size.value(), test.1, "input: {:?}", test.0);
+        assert_eq!(size.humanize().into_bytes(), size.into_bytes(), "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn parsing_exact() {
+    let tests = vec![
+        ("10 MB", Ok(Size::new(10, Multiple::Megabyte))),
+        ("1.5 KiB", Ok(Size::new(1.5, Multiple::Kibibyte))),
+        ("0 B", Ok(Size::new(0, Multiple::Byte))),
+        ("100 PiB", Ok(Size::new(100, Multiple::Pebibyte))),
+
+        ("", Err(ParsingError::EmptyInput)),
+        (" 10 MB", Err(ParsingError::MissingValue)),
+        ("10MB", Err(ParsingError::InvalidValue)),
+        ("10.0 MB", Err(ParsingError::InvalidValue)),
+        ("010 MB", Err(ParsingError::InvalidValue)),
+        ("inf MB", Err(ParsingError::InvalidValue)),
+        ("-1 B", Err(ParsingError::InvalidValue)),
+        ("-0 B", Err(ParsingError::InvalidValue)),
+        ("+1 B", Err(ParsingError::InvalidValue)),
+        ("10", Err(ParsingError::MissingMultiple)),
+        ("10 ", Err(ParsingError::MissingMultiple)),
+        ("10  MB", Err(ParsingError::InvalidMultiple)),
+        ("10 MB ", Err(ParsingError::InvalidMultiple)),
+        ("10 KB", Err(ParsingError::InvalidMultiple)),
+        ("16 bit", Err(ParsingError::InvalidMultiple)),
+        ("10 mb", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_exact(test.0);
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+        if let Ok(size) = got {
+            assert_eq!(size.to_string(), test.0, "input: {:?}", test.0);
+        }
+    }
+
+    // Accepts all `Display` output.
+    for input in &["1 GiB", "1.25 TB", "12 kB", "999.999 B"] {
+        let size: Size = input.parse().unwrap();
+        assert_eq!(Size::parse_exact(&size.to_string()), Ok(size), "input: {:?}", input);
+    }
+}
+
+#[test]
+fn parsing_without_space() {
+    let tests = vec![
+        ("1000B", Ok(Size::new(1000, Multiple::Byte))),
+        ("5GiB", Ok(Size::new(5, Multiple::Gigibyte))),
+        (" 5 GiB ", Ok(Size::new(5, Multiple::Gigibyte))),
+        ("  5GiB", Ok(Size::new(5, Multiple::Gigibyte))),
+        ("1.5kB", Ok(Size::new(1.5, Multiple::Kilobyte))),
+
+        ("GiB", Err(ParsingError::MissingValue)),
+        (" GiB", Err(ParsingError::MissingValue)),
+        ("5..5GiB", Err(ParsingError::InvalidValue)),
+        ("   ", Err(ParsingError::EmptyInput)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse::<Size>();
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn parsing_case_insensitive() {
+    let options = ParseOptions::new().case_insensitive(true);
+    let tests = vec![
+        ("1 gb", Ok(Size::new(1, Multiple::Gigabyte))),
+        ("1 Gb", Ok(Size::new(1, Multiple::Gigabyte))),
+        ("1 GiB", Ok(Size::new(1, Multiple::Gigibyte))),
+        ("1 gib", Ok(Size::new(1, Multiple::Gigibyte))),
+        ("1 KIB", Ok(Size::new(1, Multiple::Kibibyte))),
+        ("1 mb", Ok(Size::new(1, Multiple::Megabyte))),
+        ("1 MB", Ok(Size::new(1, Multiple::Megabyte))),
+        ("1 Mb", Ok(Size::new(1, Multiple::Megabyte))),
+        ("1 b", Ok(Size::new(1, Multiple::Byte))),
+        ("1 kb", Ok(Size::new(1, Multiple::Kilobyte))),
+        ("1 kB", Ok(Size::new(1, Multiple::Kilobyte))),
+        // Exact casing is parsed as usual.
+        ("1 KB", Ok(Size::new(1, Multiple::Kibibyte))),
+
+        ("1 xb", Err(ParsingError::InvalidMultiple)),
+        ("1 kbit", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_with(test.0, &options);
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+
+    let options = options.uppercase_kilo(true);
+    assert_eq!(Size::parse_with("1 KB", &options), Ok(Size::new(1, Multiple::Kilobyte).unwrap()));
+    assert_eq!("1 gb".parse::<Size>(), Err(ParsingError::InvalidMultiple));
+}
+
+#[test]
+fn parsing_single_letter() {
+    let options = ParseOptions::new().single_letter(true);
+    let tests = vec![
+        ("5G", Ok(Size::new(5, Multiple::Gigibyte))),
+        ("512K", Ok(Size::new(512, Multiple::Kibibyte))),
+        ("100M", Ok(Size::new(100, Multiple::Mebibyte))),
+        ("1.5T", Ok(Size::new(1.5, Multiple::Tebibyte))),
+        ("2P", Ok(Size::new(2, Multiple::Pebibyte))),
+        ("5 G", Ok(Size::new(5, Multiple::Gigibyte))),
+        ("12B", Ok(Size::new(12, Multiple::Byte))),
+        // Other multiples are parsed as usual.
+        ("1 KB", Ok(Size::new(1, Multiple::Kibibyte))),
+        ("1 MB", Ok(Size::new(1, Multiple::Megabyte))),
+        ("1 GiB", Ok(Size::new(1, Multiple::Gigibyte))),
+
+        ("5g", Err(ParsingError::InvalidMultiple)),
+        ("5X", Err(ParsingError::InvalidMultiple)),
+        ("G", Err(ParsingError::MissingValue)),
+    ];
+
+    for test in tests {
+        let got = Size::parse_with(test.0, &options);
+        let want = match test.1 {
+            Ok(size) => Ok(size.unwrap()),
+            Err(err) => Err(err),
+        };
+        assert_eq!(got, want, "input: {:?}", test.0);
+    }
+
+    let options = options.uppercase_kilo(true);
+    assert_eq!(Size::parse_with("1K", &options), Ok(Size::new(1, Multiple::Kibibyte).unwrap()));
+    assert_eq!(Size::parse_with("1 KB", &options), Ok(Size::new(1, Multiple::Kilobyte).unwrap()));
+    assert_eq!("5G".parse::<Size>(), Err(ParsingError::InvalidMultiple));
+}
+
+#[test]
+fn default_size() {
+    #[derive(Default)]
+    struct Config {
+        max_size: Size,
+    }
+
+    let size = Size::default();
+    assert_eq!(size, Size::new(0, Multiple::Byte).unwrap());
+    assert_eq!(size.multiple(), Multiple::Byte);
+    assert_eq!(size.to_integer::<u64>(), Ok(0u64));
+    assert_eq!(size.to_string(), "0 B");
+    assert_eq!(Config::default().max_size, size);
+}
+
+#[test]
+fn size_constants() {
+    const ZERO: Size = Size::ZERO;
+    const MAX: Size = Size::MAX;
+
+    assert!(ZERO < MAX);
+    assert_eq!(ZERO.as_bytes(), 0);
+    assert_eq!(ZERO, Size::default());
+    assert_eq!(ZERO.to_string(), "0 B");
+    assert_eq!(MAX.as_bytes(), u128::MAX);
+    assert_eq!(MAX.into_bytes(), f64::INFINITY);
+    assert_eq!(MAX.checked_add(Size::new(1, Multiple::Byte).unwrap()), Some(MAX));
+    assert_eq!(MAX.checked_add(Size::new(1, Multiple::Pebibyte).unwrap()), Some(MAX));
+    assert_eq!(MAX.checked_add(MAX), None);
+    assert_eq!(MAX.checked_sub(Size::new(1, Multiple::Byte).unwrap()), Some(MAX));
+
+    let tests = vec![
+        Size::new(1, Multiple::Byte),
+        Size::new(f64::MAX, Multiple::Byte),
+        Size::new(f64::MAX, Multiple::Terabyte),
+        Size::new(f64::MAX, Multiple::Petabyte),
+        Size::new(f64::MAX, Multiple::Pebibyte),
+    ];
+    for test in tests {
+        let size = test.unwrap();
+        assert!(ZERO < size, "input: {:?}", size);
+        assert!(size <= MAX, "input: {:?}", size);
+    }
+}
@@ -0,0 +1,108 @@
+extern crate human_size;
+
+use std::time::Duration;
+
+use human_size::*;
+
+#[test]
+fn should_parse_throughput() {
+    let tests = vec![
+        ("10 MB/s", Ok(10_000_000.0)),
+        ("10MB/s", Ok(10_000_000.0)),
+        ("10 MB / s", Ok(10_000_000.0)),
+        ("600 MB/min", Ok(10_000_000.0)),
+        ("1 GB/h", Ok(1_000_000_000.0 / 3600.0)),
+        ("1 GB/hour", Ok(1_000_000_000.0 / 3600.0)),
+        ("1 GB/day", Ok(1_000_000_000.0 / 86_400.0)),
+        ("1 KiB/s", Ok(1024.0)),
+
+        ("10 MB", Err(ParsingError::MissingTimeUnit)),
+        ("10 MB/", Err(ParsingError::MissingTimeUnit)),
+        ("10 MB/week", Err(ParsingError::InvalidTimeUnit)),
+        ("10 MB per s", Err(ParsingError::UnknownExtra)),
+        ("10/s", Err(ParsingError::MissingMultiple)),
+        ("10 XB/s", Err(ParsingError::InvalidMultiple)),
+    ];
+
+    for test in tests {
+        let got = test.0.parse::<Throughput>().map(|t| t.bytes_per_second());
+        assert_eq!(got, test.1, "input: {:?}", test.0);
+    }
+}
+
+#[test]
+fn displaying_throughput() {
+    let tests = vec![
+        ("10 MB/s", "10 MB/s"),
+        ("600 MB/min", "600 MB/min"),
+        ("1 GB/hour", "1 GB/h"),
+        ("3 KiB/day", "3 KiB/day"),
+    ];
+
+    for test in tests {
+        let got = test.0.parse::<Throughput>().unwrap().to_string();
+        assert_eq!(got, test.1, "input: {:?}", test.0);
+    }
+
+    let size = Size::new(10, Multiple::Megabyte).unwrap();
+    let throughput = Throughput::new(size, Duration::from_secs(2));
+    assert_eq!(throughput.to_string(), "5 MB/s");
+
+    // Zero intervals.
+    let throughput = Throughput::new(size, Duration::from_secs(0));
+    assert_eq!(throughput.to_string(), "10 MB/0s");
+    assert!(throughput.to_string().parse::<Throughput>().is_err());
+    let zero = Size::new(0, Multiple::Megabyte).unwrap();
+    let throughput = Throughput::new(zero, Duration::from_secs(0));
+    assert_eq!(throughput.to_string(), "0 MB/s");
+    assert_eq!(throughput.to_string().parse::<Throughput>(), Ok(throughput));
+}
+
+#[test]
+fn comparing_throughput() {
+    let parse = |input: &str| input.parse::<Throughput>().unwrap();
+    assert_eq!(parse("10 MB/s"), parse("600 MB/min"));
+    assert_eq!(parse("1 KiB/s"), parse("1024 B/s"));
+    assert!(parse("10 MB/s") > parse("10 MB/min"));
+    assert!(parse("1 GB/day") < parse("1 MB/s"));
+
+    let mut throughputs = [
+        parse("1 GB/s"),
+        parse("36 GB/h"),
+        parse("100 kB/s"),
+        parse("1 MiB/s"),
+        parse("0 B/s"),
+        parse("1 MB/s"),
+    ];
+    throughputs.sort();
+    let got: Vec<String> = throughputs.iter().map(|t| t.to_string()).collect();
+    assert_eq!(got, vec!["0 B/s", "100 kB/s", "1 MB/s", "1 MiB/s", "36 GB/h", "1 GB/s"]);
+
+    // Zero intervals.
+    let size = Size::new(1, Multiple::Byte).unwrap();
+    let zero = Size::new(0, Multiple::Byte).unwrap();
+    let infinite = Throughput::new(size, Duration::from_secs(0));
+    assert_eq!(infinite.bytes_per_second(), f64::INFINITY);
+    assert_eq!(infinite, Throughput::new(Size::new(1, Multiple::Petabyte).unwrap(), Duration::from_secs(0)));
+    assert!(infinite > parse("1000 PB/s"));
+    assert_eq!(Throughput::new(zero, Duration::from_secs(0)), parse("0 B/s"));
+}
+
+#[test]
+fn size_rate_over_duration() {
+    let tests = vec![
+        (Size::new(100, Multiple::Megabyte), Duration::from_secs(4), 25_000_000.0),
+        (Size::new(1, Multiple::Gigibyte), Duration::from_secs(8), 134_217_728.0),
+        (Size::new(3, Multiple::Kilobyte), Duration::from_millis(1500), 2000.0),
+        (Size::new(0, Multiple::Byte), Duration::from_secs(10), 0.0),
+        (Size::new(0, Multiple::Byte), Duration::from_secs(0), 0.0),
+        (Size::new(1, Multiple::Byte), Duration::from_secs(0), f64::INFINITY),
+    ];
+
+    for test in tests {
+        let size = test.0.unwrap();
+        let throughput = size.rate_over(test.1);
+        assert_eq!(throughput.bytes_per_second(), test.2, "input: {:?}, {:?}", size, test.1);
+        assert_eq!(throughput, Throughput::new(size, test.1), "input: {:?}, {:?}", size, test.1);
+    }
+}